@@ -0,0 +1,75 @@
+//! Helpers for build scripts (`build.rs`) that link to the JVM's native library.
+
+use std::path::{Path, PathBuf};
+
+use crate::{JavaHome, Result, TargetOs};
+
+/// Prints the cargo directives to link the crate being built to the native library of a Java installation.
+///
+/// The native library is located for the compilation target (from `CARGO_CFG_TARGET_OS`), or for the built platform if that is unset or unrecognized. The following directives are printed:
+/// * `cargo:rustc-link-search=native=<dir>` for the folder containing the native library (and, on Windows, for the installation's `lib` folder if it contains the `jvm.lib` import library)
+/// * `cargo:rustc-link-lib=dylib=<name>` for the library (`jvm`, or `jli` on MacOS)
+/// * `cargo:rerun-if-env-changed=JAVA_HOME`, so a different installation is picked up after `JAVA_HOME` changes
+///
+/// This only affects linking. At runtime the system loader must still be able to find the library, which is generally not the case as JDKs are not installed to a system library directory - set `LD_LIBRARY_PATH` (Linux), `DYLD_LIBRARY_PATH` (MacOS), or `PATH` (Windows) to the library's folder when running, embed an rpath, or load the library at runtime instead (see `JavaHome::load_native_library()` with the `libloading` feature).
+///
+/// Paths that are not valid UTF-8 are printed lossily.
+///
+/// # Errors
+/// This function will error if the native library could not be located, in any situation that `JavaHome::native_library()` will.
+pub fn emit_link_directives(home: &JavaHome) -> Result<()> {
+	let target = std::env::var("CARGO_CFG_TARGET_OS").ok().and_then(|os| TargetOs::from_cfg(&os));
+	let library = match target {
+		Some(target) => home.native_library_for(target)?,
+		None => home.native_library()?,
+	};
+
+	for dir in link_search_dirs(home, &library, target) {
+		println!("cargo:rustc-link-search=native={}", dir.display());
+	}
+	println!("cargo:rustc-link-lib=dylib={}", link_name(&library));
+	println!("cargo:rerun-if-env-changed={}", JavaHome::ENV_VAR);
+	Ok(())
+}
+
+/// The folders to search when linking to the native library.
+fn link_search_dirs(home: &JavaHome, library: &Path, target: Option<TargetOs>) -> Vec<PathBuf> {
+	let mut dirs: Vec<PathBuf> = library.parent().map(Path::to_path_buf).into_iter().collect();
+
+	// MSVC links against the import library, which JDKs keep separately from the DLL
+	let windows = target.map_or(cfg!(target_os = "windows"), |target| target == TargetOs::Windows);
+	let lib = home.path.join("lib");
+	if windows && lib.join("jvm.lib").is_file() {
+		dirs.push(lib);
+	}
+
+	dirs
+}
+
+/// The name to link a library by, without its `lib` prefix and extension (`libjvm.so` becomes `jvm`).
+fn link_name(library: &Path) -> String {
+	let stem = library.file_stem().map(|stem| stem.to_string_lossy()).unwrap_or_default();
+	stem.strip_prefix("lib").unwrap_or(&stem).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn links_to_server_variant_for_target() {
+		let dir = tempfile::tempdir().unwrap();
+		for variant in &["client", "server"] {
+			let folder = dir.path().join("lib").join(variant);
+			std::fs::create_dir_all(&folder).unwrap();
+			std::fs::write(folder.join("libjvm.so"), "").unwrap();
+		}
+		let home = JavaHome::assume(dir.path());
+
+		// as `emit_link_directives()` would with `CARGO_CFG_TARGET_OS=linux`
+		let target = TargetOs::from_cfg("linux");
+		let library = home.native_library_for(target.unwrap()).unwrap();
+		assert_eq!(link_search_dirs(&home, &library, target), vec![dir.path().join("lib").join("server")]);
+		assert_eq!(link_name(&library), "jvm");
+	}
+}
@@ -0,0 +1,186 @@
+//! Minimal ELF parsing, for inspecting the dynamic dependencies of native libraries without external tools.
+
+use std::convert::{TryFrom, TryInto};
+
+const PT_LOAD: u32 = 1;
+const PT_DYNAMIC: u32 = 2;
+const DT_NULL: u64 = 0;
+const DT_NEEDED: u64 = 1;
+const DT_STRTAB: u64 = 5;
+
+/// Reads little or big endian integers of the ELF file's word size.
+struct Reader<'a> {
+	data: &'a [u8],
+	is_64: bool,
+	is_le: bool,
+}
+impl<'a> Reader<'a> {
+	fn bytes<const N: usize>(&self, offset: usize) -> Option<[u8; N]> {
+		self.data.get(offset..offset.checked_add(N)?)?.try_into().ok()
+	}
+	fn u16(&self, offset: usize) -> Option<u16> {
+		let b = self.bytes(offset)?;
+		Some(if self.is_le { u16::from_le_bytes(b) } else { u16::from_be_bytes(b) })
+	}
+	fn u32(&self, offset: usize) -> Option<u32> {
+		let b = self.bytes(offset)?;
+		Some(if self.is_le { u32::from_le_bytes(b) } else { u32::from_be_bytes(b) })
+	}
+	fn u64(&self, offset: usize) -> Option<u64> {
+		let b = self.bytes(offset)?;
+		Some(if self.is_le { u64::from_le_bytes(b) } else { u64::from_be_bytes(b) })
+	}
+	/// Reads an address/offset sized field (`Elf32_Addr`/`Elf64_Addr`, etc)
+	fn word(&self, offset: usize) -> Option<u64> {
+		if self.is_64 { self.u64(offset) } else { self.u32(offset).map(u64::from) }
+	}
+	fn cstr(&self, offset: usize) -> Option<&'a str> {
+		let tail = self.data.get(offset..)?;
+		let len = tail.iter().position(|&b| b == 0)?;
+		std::str::from_utf8(&tail[..len]).ok()
+	}
+}
+
+/// A program header's type, file offset, virtual address, and size within the file.
+struct Segment {
+	kind: u32,
+	offset: u64,
+	vaddr: u64,
+	filesz: u64,
+}
+
+/// Returns the `DT_NEEDED` entries (the sonames of required shared libraries) of an ELF file, or `None` if the data is not a well-formed ELF file.
+///
+/// Files without a dynamic section (such as static executables) have no dependencies.
+pub(crate) fn needed_libraries(data: &[u8]) -> Option<Vec<String>> {
+	if data.get(..4)? != b"\x7fELF" {
+		return None;
+	}
+	let r = Reader {
+		data,
+		is_64: *data.get(4)? == 2,
+		is_le: *data.get(5)? == 1,
+	};
+
+	let (phoff, phentsize, phnum) = if r.is_64 {
+		(r.u64(0x20)?, r.u16(0x36)?, r.u16(0x38)?)
+	} else {
+		(u64::from(r.u32(0x1C)?), r.u16(0x2A)?, r.u16(0x2C)?)
+	};
+
+	let segments = (0..usize::from(phnum))
+		.map(|i| {
+			let base = usize::try_from(phoff).ok()?.checked_add(i.checked_mul(usize::from(phentsize))?)?;
+			// keeps the field offsets below from overflowing
+			if base >= data.len() {
+				return None;
+			}
+			Some(if r.is_64 {
+				Segment { kind: r.u32(base)?, offset: r.u64(base + 0x08)?, vaddr: r.u64(base + 0x10)?, filesz: r.u64(base + 0x20)? }
+			} else {
+				Segment { kind: r.u32(base)?, offset: r.word(base + 0x04)?, vaddr: r.word(base + 0x08)?, filesz: r.word(base + 0x10)? }
+			})
+		})
+		.collect::<Option<Vec<Segment>>>()?;
+
+	let dynamic = match segments.iter().find(|s| s.kind == PT_DYNAMIC) {
+		Some(dynamic) => dynamic,
+		None => return Some(Vec::new()),
+	};
+
+	// collect the dynamic entries we care about, up to DT_NULL
+	let entry_size = if r.is_64 { 16 } else { 8 };
+	let mut needed = Vec::new();
+	let mut strtab = None;
+	let start = usize::try_from(dynamic.offset).ok()?;
+	for i in 0..usize::try_from(dynamic.filesz).ok()? / entry_size {
+		let base = start.checked_add(i * entry_size)?;
+		if base >= data.len() {
+			return None;
+		}
+		let tag = r.word(base)?;
+		let val = r.word(base + entry_size / 2)?;
+		match tag {
+			DT_NULL => break,
+			DT_NEEDED => needed.push(val),
+			DT_STRTAB => strtab = Some(val),
+			_ => {},
+		}
+	}
+
+	// DT_STRTAB is a virtual address, find where it was loaded from
+	let strtab = strtab?;
+	let strtab_offset = segments.iter()
+		.filter(|s| s.kind == PT_LOAD)
+		.find(|s| s.vaddr <= strtab && strtab - s.vaddr < s.filesz)
+		.and_then(|s| (strtab - s.vaddr).checked_add(s.offset))?;
+
+	needed.into_iter()
+		.map(|name| r.cstr(usize::try_from(strtab_offset.checked_add(name)?).ok()?).map(String::from))
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// A little endian ELF64 header, with `phnum` program headers at `phoff`.
+	fn header(phoff: u64, phnum: u16) -> Vec<u8> {
+		let mut data = vec![0u8; 64];
+		data[..6].copy_from_slice(b"\x7fELF\x02\x01");
+		data[0x20..0x28].copy_from_slice(&phoff.to_le_bytes());
+		data[0x36..0x38].copy_from_slice(&56u16.to_le_bytes());
+		data[0x38..0x3A].copy_from_slice(&phnum.to_le_bytes());
+		data
+	}
+
+	fn segment(data: &mut Vec<u8>, kind: u32, offset: u64, filesz: u64) {
+		let mut header = vec![0u8; 56];
+		header[..4].copy_from_slice(&kind.to_le_bytes());
+		header[0x08..0x10].copy_from_slice(&offset.to_le_bytes());
+		header[0x10..0x18].copy_from_slice(&offset.to_le_bytes());
+		header[0x20..0x28].copy_from_slice(&filesz.to_le_bytes());
+		data.extend_from_slice(&header);
+	}
+
+	#[test]
+	fn reads_needed_libraries() {
+		let mut data = header(64, 2);
+		segment(&mut data, PT_LOAD, 0, 235);
+		segment(&mut data, PT_DYNAMIC, 176, 48);
+		for (tag, val) in &[(DT_NEEDED, 1u64), (DT_STRTAB, 224), (DT_NULL, 0)] {
+			data.extend_from_slice(&tag.to_le_bytes());
+			data.extend_from_slice(&val.to_le_bytes());
+		}
+		data.extend_from_slice(b"\0libc.so.6\0");
+		assert_eq!(data.len(), 235);
+
+		assert_eq!(needed_libraries(&data), Some(vec!["libc.so.6".to_string()]));
+	}
+
+	#[test]
+	fn malformed_files_are_rejected() {
+		let samples: Vec<Vec<u8>> = vec![
+			Vec::new(),
+			b"MZ\x90\0".to_vec(),
+			// truncated within the header
+			b"\x7fELF\x02\x01\x01\0".to_vec(),
+			// program headers past the end of the file, or at an offset that overflows
+			header(4096, 1),
+			header(u64::MAX, 2),
+		];
+		for data in &samples {
+			assert_eq!(needed_libraries(data), None, "{:?}", data);
+		}
+
+		// a string table address that overflows when added to the segment's size
+		let mut data = header(64, 2);
+		segment(&mut data, PT_LOAD, u64::MAX - 8, u64::MAX);
+		segment(&mut data, PT_DYNAMIC, 176, 32);
+		for (tag, val) in &[(DT_NEEDED, 1u64), (DT_STRTAB, u64::MAX)] {
+			data.extend_from_slice(&tag.to_le_bytes());
+			data.extend_from_slice(&val.to_le_bytes());
+		}
+		assert_eq!(needed_libraries(&data), None);
+	}
+}
@@ -0,0 +1,102 @@
+use crate::{Error, JavaHome, Result};
+
+/// A place that a [`Finder`] can search for a Java installation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HomeSource {
+	/// The `JAVA_HOME` environment variable, if it points to an existing directory containing a `bin/java` executable. See `JavaHome::find_valid_home()`.
+	EnvVar,
+	/// The `HKLM\SOFTWARE\JavaSoft` registry keys written by Windows installers. See `JavaHome::installations_from_registry()`.
+	///
+	/// This source only finds installations on Windows with the `registry` feature enabled, and otherwise has nothing to offer.
+	Registry,
+	/// Well-known system installation directories. See `JavaHome::system_installations()`.
+	SystemDirs,
+	/// The first `java` executable on the system path. See `JavaHome::find_active_home()`.
+	PathProbe,
+}
+impl HomeSource {
+	/// Searches this source for a Java installation, returning `None` if the source has nothing to offer.
+	///
+	/// # Errors
+	/// This function will error in the same situations as the underlying `JavaHome` function noted on each variant.
+	pub fn find(self) -> Result<Option<JavaHome>> {
+		match self {
+			HomeSource::EnvVar => JavaHome::valid_env_home(),
+			#[cfg(all(target_os = "windows", feature = "registry"))]
+			HomeSource::Registry => Ok(JavaHome::installations_from_registry()?.into_iter().next()),
+			#[cfg(not(all(target_os = "windows", feature = "registry")))]
+			HomeSource::Registry => Ok(None),
+			HomeSource::SystemDirs => Ok(JavaHome::system_installations()?.into_iter().next()),
+			HomeSource::PathProbe => JavaHome::find_active_home().map(Some),
+		}
+	}
+}
+
+/// Searches a configurable, ordered list of sources for a Java installation.
+///
+/// ```no_run
+/// use jvm_find::{Finder, HomeSource};
+///
+/// let home = Finder::new()
+///     .preference([HomeSource::EnvVar, HomeSource::Registry, HomeSource::SystemDirs, HomeSource::PathProbe])
+///     .find()?;
+/// # Ok::<(), jvm_find::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Finder {
+	preference: Vec<HomeSource>,
+}
+impl Finder {
+	/// The default source ordering. This matches `JavaHome::find_valid_home()`, then falls back to the registry and system directories.
+	pub const DEFAULT_PREFERENCE: &'static [HomeSource] = &[
+		HomeSource::EnvVar,
+		HomeSource::PathProbe,
+		HomeSource::Registry,
+		HomeSource::SystemDirs,
+	];
+
+	/// Creates a finder using `Finder::DEFAULT_PREFERENCE`.
+	pub fn new() -> Finder {
+		Finder {
+			preference: Finder::DEFAULT_PREFERENCE.to_vec(),
+		}
+	}
+
+	/// Replaces the order that sources are searched in. Sources not listed are not searched.
+	pub fn preference(mut self, sources: impl IntoIterator<Item = HomeSource>) -> Finder {
+		self.preference = sources.into_iter().collect();
+		self
+	}
+
+	/// Searches each source in order, returning the first installation found.
+	///
+	/// Errors from individual sources are logged and the next source is tried.
+	///
+	/// # Errors
+	/// If no source finds an installation, this returns `Error::AllSourcesFailed` with each source's error, in order. Sources that had nothing to offer are listed with `Error::NoInstallationsFound`. If there are no sources to search, `Error::NoInstallationsFound` is returned directly.
+	pub fn find(&self) -> Result<JavaHome> {
+		let mut errors = Vec::with_capacity(self.preference.len());
+
+		for &source in &self.preference {
+			log::debug!("searching {:?} for a java installation", source);
+			match source.find() {
+				Ok(Some(home)) => return Ok(home),
+				Ok(None) => errors.push((source, Error::NoInstallationsFound)),
+				Err(e) => {
+					log::debug!("\tfailed: {}", e);
+					errors.push((source, e));
+				},
+			}
+		}
+
+		if errors.is_empty() {
+			return Err(Error::NoInstallationsFound);
+		}
+		Err(Error::AllSourcesFailed(errors))
+	}
+}
+impl Default for Finder {
+	fn default() -> Finder {
+		Finder::new()
+	}
+}
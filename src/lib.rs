@@ -1,10 +1,36 @@
-
 #![doc(html_root_url = "https://docs.rs/jvm-find/0.1.1")]
 
+use std::collections::{BTreeMap, HashMap};
+use std::ffi::{OsStr, OsString};
 use std::io::ErrorKind;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::ops::Deref;
+#[cfg(feature = "glob")]
+use std::sync::OnceLock;
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+
+#[cfg(feature = "glob")]
+mod elf;
+mod finder;
+#[cfg(feature = "glob")]
+mod macho;
+#[cfg(feature = "async")]
+mod nonblocking;
+#[cfg(feature = "glob")]
+mod pe;
+mod summary;
+mod version;
+mod walk;
+pub use finder::{Finder, HomeSource};
+pub use summary::{JavaInstallation, JavaSummary};
+pub use version::JavaVersion;
+
+#[cfg(feature = "build")]
+pub mod build;
+#[cfg(any(test, feature = "test-fixtures"))]
+pub mod test_fixtures;
 
 // TODO: apply #[doc(cfg(feature = "glob"))] to applicable features when stabilized (#43781)
 
@@ -13,6 +39,9 @@ pub enum Error {
 	#[error("Error running Java executable on system path")]
 	JavaExecution(#[source] std::io::Error),
 
+	#[error("No Java executable could be found to run")]
+	JavaNotFound,
+
 	#[error("Error accessing JavaHome path")]
 	IoError(#[source] std::io::Error),
 
@@ -23,21 +52,98 @@ pub enum Error {
 	#[error("The installed java executable did not report a `java.home` property")]
 	NoJavaHomeProperty,
 
+	#[error("The Java executable did not finish within {0:?}")]
+	JavaTimeout(Duration),
+
+	#[error("The Java executable ran, but produced no output")]
+	EmptyProbeOutput,
+
+	#[error("The Java executable does not support -XshowSettings (added in Java 7), so its home could not be queried")]
+	ShowSettingsUnsupported,
+
+	#[error("No Java installation could be found")]
+	NoInstallationsFound,
+
+	#[error("No source could find a Java installation ({})", format_source_errors(.0))]
+	AllSourcesFailed(Vec<(HomeSource, Error)>),
+
+	#[error("No finder could find a Java installation ({})", format_finder_errors(.0))]
+	AllFindersFailed(Vec<Error>),
+
+	#[error("Unable to determine the version of the Java installation")]
+	NoVersionInformation,
+
+	#[error("Unable to determine the architecture of the Java installation")]
+	NoArchitectureInformation,
+
+	#[error("Unable to determine the default file encoding of the Java installation")]
+	NoEncodingInformation,
+
+	#[error("Unable to determine the vendor of the Java installation")]
+	NoVendorInformation,
+
+	#[error("Unable to determine the JVM flags of the Java installation")]
+	NoFlagsInformation,
+
+	#[error("Unable to parse Java version string {0:?}")]
+	VersionParse(String),
+
+	#[error("The Java installation is version {found}, but at least version {required} is required")]
+	VersionTooOld { found: JavaVersion, required: u32 },
+
+	// the glob helpers fall back to walking the directory tree for non-utf8 paths, rather than returning this
 	#[cfg(feature = "glob")]
-	#[error("Attempted to perform an operation with a non-utf8 path that does not support non-utf8 paths")]
+	#[error("Attempted to perform an operation with a non-utf8 path that does not support non-utf8 paths (bad path: {})", .0.display())]
 	PathNotUTF8(PathBuf),
 
 	#[cfg(feature = "glob")]
 	#[error("Unspecified error while globbing JAVA_HOME")]
 	GlobError(#[from] glob::GlobError),
 
+	#[cfg(feature = "glob")]
+	#[error("Invalid glob pattern")]
+	GlobPattern(#[from] glob::PatternError),
+
 	#[cfg(feature = "glob")]
 	#[error("Unable to find native library file within JAVA_HOME")]
 	NoNativeLibrary,
+
+	#[cfg(feature = "libloading")]
+	#[error("Unable to load the JVM native library")]
+	NativeLibraryLoadFailed(#[source] libloading::Error),
+
+	#[cfg(target_os = "macos")]
+	#[error("/usr/libexec/java_home could not find a Java installation ({0})")]
+	MacosJavaHomeFailed(String),
+
+	#[cfg(any(target_os = "macos", target_os = "windows"))]
+	#[error("Unable to run the platform's code signature verification tool")]
+	SignatureToolFailed(#[source] std::io::Error),
 }
 
 type Result<T> = std::result::Result<T, Error>;
 
+/// Formats each source's error for `Error::AllSourcesFailed`.
+fn format_source_errors(errors: &[(HomeSource, Error)]) -> String {
+	errors.iter()
+		.map(|(source, e)| format!("{:?}: {}", source, e))
+		.collect::<Vec<_>>()
+		.join("; ")
+}
+
+/// Formats each finder's error for `Error::AllFindersFailed`.
+fn format_finder_errors(errors: &[Error]) -> String {
+	errors.iter()
+		.map(ToString::to_string)
+		.collect::<Vec<_>>()
+		.join("; ")
+}
+
+/// Appends the platform's executable suffix (`.exe` on Windows) to a tool name.
+fn executable_name(name: &str) -> String {
+	format!("{}{}", name, std::env::consts::EXE_SUFFIX)
+}
+
 /// The Windows native library that applications can link to - `jvm.dll`
 pub const NATIVE_LIBRARY_FILENAME_WIN: &str = "jvm.dll";
 /// The Linux shared library object that applications can link to - `libjvm.so`
@@ -63,174 +169,3120 @@ pub const NATIVE_LIBRARY_FILENAME: &str = NATIVE_LIBRARY_FILENAME_LIN;
 #[cfg(target_os = "macos")]
 pub const NATIVE_LIBRARY_FILENAME: &str = NATIVE_LIBRARY_FILENAME_MAC;
 
-/// A located Java home directory. Note that this type does not necessarily contain a valid one (in the case of a bad JAVA_HOME variable, custom-created one, etc) - as such, all usages of the contained path must be validated.
+/// The operating system whose native library is `NATIVE_LIBRARY_FILENAME`.
+#[cfg(all(feature = "glob", target_os = "windows"))]
+const BUILT_TARGET_OS: TargetOs = TargetOs::Windows;
+#[cfg(all(feature = "glob", any(
+	target_os = "freebsd",
+	target_os = "linux",
+	target_os = "netbsd",
+	target_os = "openbsd"
+)))]
+const BUILT_TARGET_OS: TargetOs = TargetOs::Linux;
+#[cfg(all(feature = "glob", target_os = "macos"))]
+const BUILT_TARGET_OS: TargetOs = TargetOs::MacOs;
+
+/// An operating system that a Java installation may be built for, to select its native library with `JavaHome::native_library_for()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TargetOs {
+	/// Windows, with the `jvm.dll` native library.
+	Windows,
+	/// Linux, with the `libjvm.so` native library.
+	Linux,
+	/// MacOS, with the `libjli.dylib` native library.
+	MacOs,
+}
+impl TargetOs {
+	/// Maps a `target_os` configuration value (such as from the `CARGO_CFG_TARGET_OS` environment variable in build scripts) to an operating system, or `None` if it is not one of `windows`, `linux`, or `macos`.
+	pub fn from_cfg(target_os: &str) -> Option<TargetOs> {
+		match target_os {
+			"windows" => Some(TargetOs::Windows),
+			"linux" => Some(TargetOs::Linux),
+			"macos" => Some(TargetOs::MacOs),
+			_ => None,
+		}
+	}
+
+	/// The native library filename for this operating system. (`jvm.dll`/`libjvm.so`/`libjli.dylib`)
+	pub fn native_library_filename(self) -> &'static str {
+		match self {
+			TargetOs::Windows => NATIVE_LIBRARY_FILENAME_WIN,
+			TargetOs::Linux => NATIVE_LIBRARY_FILENAME_LIN,
+			TargetOs::MacOs => NATIVE_LIBRARY_FILENAME_MAC,
+		}
+	}
+}
+
+/// A HotSpot VM variant, which installations may ship separate native libraries for (in `server` and `client` folders).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum VmVariant {
+	/// The server VM, optimized for peak performance. This is the only variant in most modern installations.
+	Server,
+	/// The client VM, optimized for startup time. Only shipped by some older and 32-bit installations.
+	Client,
+}
+impl VmVariant {
+	/// The name of the folder holding this variant's native library (`server` or `client`).
+	pub fn folder_name(self) -> &'static str {
+		match self {
+			VmVariant::Server => "server",
+			VmVariant::Client => "client",
+		}
+	}
+}
+
+/// The processor architecture a Java installation was built for, as reported by `JavaHome::arch()`.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum JvmArch {
+	/// 32-bit x86 (`x86`, `i386`, etc).
+	X86,
+	/// 64-bit x86 (`amd64` or `x86_64`).
+	X86_64,
+	/// 64-bit ARM (`aarch64` or `arm64`).
+	Aarch64,
+	/// Any other architecture, holding its unparsed name (such as `ppc64le` or `s390x`).
+	Other(String),
+}
+impl JvmArch {
+	/// Maps an `os.arch` property or `release` file `OS_ARCH` value to an architecture. Unrecognized names become `JvmArch::Other`.
+	pub fn from_os_arch(arch: &str) -> JvmArch {
+		match arch {
+			"x86" | "i386" | "i486" | "i586" | "i686" => JvmArch::X86,
+			"amd64" | "x86_64" | "x86-64" => JvmArch::X86_64,
+			"aarch64" | "arm64" => JvmArch::Aarch64,
+			other => JvmArch::Other(other.to_string()),
+		}
+	}
+
+	/// The pointer width of this architecture in bits, for comparing with the calling process's `cfg!(target_pointer_width)`. Returns `None` for `JvmArch::Other`, see `JavaHome::is_64bit()` for those.
+	pub fn pointer_width(&self) -> Option<u32> {
+		match self {
+			JvmArch::X86 => Some(32),
+			JvmArch::X86_64 | JvmArch::Aarch64 => Some(64),
+			JvmArch::Other(_) => None,
+		}
+	}
+}
+
+/// The kind of a Java installation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RuntimeKind {
+	/// A Java Development Kit, containing development tools (`javac`, etc) and headers in addition to the runtime.
+	Jdk,
+	/// A Java Runtime Environment, only able to run Java programs.
+	Jre,
+}
+
+/// The C standard library a Linux Java installation was built against.
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LibcKind {
+	/// The GNU C Library, used by most Linux distributions.
+	Glibc,
+	/// The musl C library, used by Alpine Linux and other lightweight distributions.
+	Musl,
+}
+
+/// Why the JAVA_HOME environment variable was not used, as reported by `JavaHome::find_valid_home_verbose()`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum FallbackReason {
+	/// JAVA_HOME was not set, or was empty.
+	EnvVarUnset,
+	/// JAVA_HOME did not point to an existing directory.
+	EnvVarBadPath(PathBuf),
+	/// JAVA_HOME was a symlink whose target no longer exists, such as after the installation it pointed to was removed.
+	EnvVarBrokenSymlink(PathBuf),
+	/// JAVA_HOME pointed to an existing directory without a `bin/java` executable, such as one left behind by an uninstalled JDK.
+	EnvVarNoJava(PathBuf),
+}
+impl std::fmt::Display for FallbackReason {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			FallbackReason::EnvVarUnset => write!(f, "JAVA_HOME is not set"),
+			FallbackReason::EnvVarBadPath(path) => write!(f, "JAVA_HOME is not an existing directory (bad path: {})", path.display()),
+			FallbackReason::EnvVarBrokenSymlink(path) => write!(f, "JAVA_HOME is a symlink to a target that no longer exists (bad path: {})", path.display()),
+			FallbackReason::EnvVarNoJava(path) => write!(f, "JAVA_HOME has no bin/java executable (bad path: {})", path.display()),
+		}
+	}
+}
+
+/// A command shell, for generating scripts with `JavaHome::export_script()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Shell {
+	/// POSIX-compatible shells, such as `bash`, `zsh`, or `sh`.
+	Bash,
+	/// The `fish` shell.
+	Fish,
+	/// PowerShell, on any platform.
+	PowerShell,
+	/// The Windows Command Prompt (`cmd.exe`).
+	Cmd,
+}
+
+/// A located Java home directory. Note that this type does not necessarily contain a valid one (in the case of a bad JAVA_HOME variable, custom-created one, etc) - as such, all usages of the contained path must be validated.
+///
+/// Some expensive lookups (such as `JavaHome::native_library()`) are cached within the value. If `path` is changed, or the installation is modified on disk, call `JavaHome::refresh()` to clear them.
+///
+/// Comparisons and hashing only consider the path.
+#[derive(Debug, Clone)]
 pub struct JavaHome {
 	pub path: PathBuf,
+
+	#[cfg(feature = "glob")]
+	native_library: OnceLock<PathBuf>,
 }
 impl JavaHome {
 	/// The `JAVA_HOME` environment variable name.
 	pub const ENV_VAR: &'static str = "JAVA_HOME";
 
+	/// The environment variables checked by `JavaHome::find_home_from_env()`, in order. Some toolchains and container images set `JDK_HOME` or `JRE_HOME` rather than `JAVA_HOME`.
+	pub const ENV_VARS: &'static [&'static str] = &[JavaHome::ENV_VAR, "JDK_HOME", "JRE_HOME"];
+
+	/// How long `JavaHome::find_active_home()` waits for the `java` executable to report its home.
+	pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+	/// Creates a `JavaHome` from a path without checking it at all.
+	///
+	/// The path may not exist, or may not be a Java installation - any errors will only surface when the path is later used. Prefer `JavaHome::checked()` for paths of unknown quality, such as those from user configuration.
+	pub fn assume(path: impl Into<PathBuf>) -> Self {
+		JavaHome::from(path.into())
+	}
+
+	/// Creates a `JavaHome` from a path after checking that it is an existing directory. The path is passed through `JavaHome::normalize()`, so common misconfigurations (such as pointing at the `bin` folder) are corrected.
+	///
+	/// As an exception, a path to an installation's `java` executable is accepted, and corrected to the installation's home.
+	///
+	/// # Errors
+	/// This will error with `Error::BadJavaHomePath` if the path does not exist or is not a directory, or with `Error::IoError` if it is unreachable (due to permissions/broken links/etc errors).
+	pub fn checked(path: impl Into<PathBuf>) -> Result<Self> {
+		let path = path.into();
+		match path.metadata() {
+			Err(e) if e.kind() != ErrorKind::NotFound => Err(Error::IoError(e)),
+			Ok(meta) if meta.is_dir() => Ok(JavaHome::assume(path).normalize()),
+			Ok(meta) if meta.is_file() => {
+				// pointing at bin/java itself is a common mistake
+				let home = JavaHome::assume(&path).normalize();
+				if home.path != path { Ok(home) } else { Err(Error::BadJavaHomePath(path)) }
+			},
+			_ => Err(Error::BadJavaHomePath(path)),
+		}
+	}
+
+	/// Creates a `JavaHome` from a path without checking it, such as one read from configuration that is trusted. This is the same as `JavaHome::assume()`.
+	pub fn from_path(path: impl Into<PathBuf>) -> Self {
+		JavaHome::assume(path)
+	}
+
+	/// Creates a `JavaHome` from a path after checking that it is an installation: an existing directory (as with `JavaHome::checked()`, including its corrections) that contains a `bin/java` executable.
+	///
+	/// # Errors
+	/// This will error with `Error::BadJavaHomePath` if the path is not an existing directory or has no `bin/java` executable, or with `Error::IoError` if either is unreachable (due to permissions/broken links/etc errors).
+	pub fn validate(path: impl Into<PathBuf>) -> Result<Self> {
+		let home = JavaHome::checked(path)?;
+		match home.java_executable().metadata() {
+			Ok(meta) if meta.is_file() => Ok(home),
+			Err(e) if e.kind() != ErrorKind::NotFound => Err(Error::IoError(e)),
+			_ => Err(Error::BadJavaHomePath(home.path)),
+		}
+	}
+
+	/// Returns a home for a copy of this installation at a new location, such as after bundling it into a package.
+	///
+	/// Nothing cached about this home (like the located native library) is carried over, so every path from the returned home is found within `new_root`. Like `JavaHome::assume()`, the new root is not checked.
+	pub fn relocate(&self, new_root: PathBuf) -> JavaHome {
+		JavaHome::assume(new_root)
+	}
+
+	/// Returns a home for this installation's canonical path, with every symlink and `.`/`..` component resolved (see `std::fs::canonicalize()`). Homes reached through different links (such as `/usr/lib/jvm/default-java`) then compare equal.
+	///
+	/// This shadows `Path::canonicalize()`, which is reachable through `Deref`.
+	///
+	/// # Errors
+	/// This function will error with `Error::IoError` if the path does not exist or could not be resolved.
+	pub fn canonicalize(&self) -> Result<JavaHome> {
+		self.path.canonicalize().map(JavaHome::assume).map_err(Error::IoError)
+	}
+
+	/// Returns this home's path relative to `base`, or `None` if the home is not within `base`. This is useful for recording a toolchain location portably, such as relative to a workspace root.
+	///
+	/// Paths are compared by their components as-is, without resolving symlinks or `..` components. This shadows `Path::strip_prefix()`, which is reachable through `Deref`.
+	pub fn strip_prefix(&self, base: &Path) -> Option<PathBuf> {
+		self.path.strip_prefix(base).ok().map(PathBuf::from)
+	}
+
+	/// Runs each finder in order (such as `JavaHome::find_valid_home`, or closures validating application-specific paths), returning the first installation found.
+	///
+	/// This composes custom search orders from arbitrary functions. For searching the crate's built-in sources in a custom order, see `Finder`.
+	///
+	/// # Errors
+	/// If every finder fails, this returns `Error::AllFindersFailed` with each finder's error, in order. If there are no finders, `Error::NoInstallationsFound` is returned instead.
+	pub fn first_ok(finders: &[&dyn Fn() -> Result<JavaHome>]) -> Result<JavaHome> {
+		if finders.is_empty() {
+			return Err(Error::NoInstallationsFound);
+		}
+
+		let mut errors = Vec::with_capacity(finders.len());
+		for finder in finders {
+			match finder() {
+				Ok(home) => return Ok(home),
+				Err(e) => {
+					log::debug!("finder failed: {}", e);
+					errors.push(e);
+				},
+			}
+		}
+		Err(Error::AllFindersFailed(errors))
+	}
+
+	/// Returns the environment variables that are currently set (and non-empty) which silently change the behavior of any JVM that is run, along with their values.
+	///
+	/// These are `_JAVA_OPTIONS`, `JAVA_TOOL_OPTIONS`, and `JDK_JAVA_OPTIONS` (which add JVM options), and `CLASSPATH` (which changes the default class path). Non-UTF-8 values are converted lossily.
+	pub fn interfering_env_vars() -> Vec<(String, String)> {
+		["_JAVA_OPTIONS", "JAVA_TOOL_OPTIONS", "JDK_JAVA_OPTIONS", "CLASSPATH"].iter()
+			.filter_map(|&name| {
+				std::env::var_os(name)
+					.filter(|value| !value.is_empty())
+					.map(|value| (name.to_string(), value.to_string_lossy().into_owned()))
+			})
+			.collect()
+	}
+
 	/// Returns the existing JAVA_HOME environment variable if it is non-empty, or queries the active Java installation.
 	///
+	/// The JAVA_HOME environment variable is used as-is (see `JavaHome::assume()`), and may not point to a valid installation. Use `JavaHome::find_valid_home()` to check it.
+	///
+	/// On MacOS, the installation selected by `/usr/libexec/java_home` (see `JavaHome::find_macos_java_home()`) is preferred over running `java`, as the system's `java` is only a stub which prompts to install a JDK when none is installed.
+	///
 	/// # Errors
 	/// This function will error in any situation that `JavaHome::find_active_home()` will - refer to that function for detailed error cases.
 	pub fn find_home() -> Result<Self> {
 		std::env::var_os(JavaHome::ENV_VAR)
 			.filter(|var| !var.is_empty())
 			.map(PathBuf::from)
-			.map(|path| Ok(JavaHome { path }))
-			.unwrap_or_else(JavaHome::find_active_home)
+			.map(|path| Ok(JavaHome::assume(path)))
+			.unwrap_or_else(JavaHome::find_platform_home)
 	}
 
-	/// Checks that any existing JAVA_HOME environment variable points to a valid directory, and if not, it falls back to the currently active Java installation's home directory.
+	/// Like `JavaHome::find_home()`, but checks each of `JavaHome::ENV_VARS` in order (`JAVA_HOME`, then `JDK_HOME`, then `JRE_HOME`), returning the first that is non-empty. The active Java installation is only queried if none are set.
+	///
+	/// As with `JavaHome::find_home()`, the variable is used as-is and may not point to a valid installation.
 	///
 	/// # Errors
-	/// This will error if the directory specified by JAVA_HOME is unreachable (due to permissions/broken links/etc errors). Also contains error conditions as specified by `JavaHome::find_active_home()`
-	pub fn find_valid_home() -> Result<Self> {
-		std::env::var_os(JavaHome::ENV_VAR)
-			.filter(|var| !var.is_empty())
-			.map(PathBuf::from)
-			.map(|path| {
-				match path.metadata() {
-					// bubble up IO (permission/etc) errors
-					Err(e) if e.kind() != ErrorKind::NotFound => Some(Err(Error::IoError(e))),
+	/// This function will error in any situation that `JavaHome::find_active_home()` will.
+	pub fn find_home_from_env() -> Result<Self> {
+		JavaHome::ENV_VARS.iter()
+			.find_map(|name| {
+				let var = std::env::var_os(name).filter(|var| !var.is_empty())?;
+				log::debug!("using the {} environment variable", name);
+				Some(var)
+			})
+			.map(|path| Ok(JavaHome::assume(path)))
+			.unwrap_or_else(JavaHome::find_platform_home)
+	}
 
-					// return Some(pb) iff this is an existing directory
-					Ok(meta) if meta.is_dir() => Some(Ok(JavaHome { path })),
+	/// Finds the platform's preferred Java installation for when JAVA_HOME is unset.
+	fn find_platform_home() -> Result<Self> {
+		#[cfg(target_os = "macos")]
+		match JavaHome::find_macos_java_home(None) {
+			Ok(home) => return Ok(home),
+			Err(e) => log::debug!("/usr/libexec/java_home failed ({}), running java instead", e),
+		}
 
-					// It's not a directory? outdated env var? Query java executable
-					_ => None
-				}
-			})
-			.flatten().transpose()?.map(Ok)
+		JavaHome::find_active_home()
+	}
+
+	/// Finds a Java installation with MacOS's `/usr/libexec/java_home` tool, which resolves the user's preferred installation. If a version (such as `17` or `1.8`) is provided, it is passed with `-v` to select the preferred installation of that version instead.
+	///
+	/// # Errors
+	/// This function will error with `Error::JavaExecution` if the tool could not be run, or with `Error::MacosJavaHomeFailed` (holding the tool's message) if it exits unsuccessfully, such as when no installation (of the requested version) exists.
+	#[cfg(target_os = "macos")]
+	pub fn find_macos_java_home(version: Option<&str>) -> Result<Self> {
+		let mut command = Command::new("/usr/libexec/java_home");
+		if let Some(version) = version {
+			command.arg("-v").arg(version);
+		}
+		log::debug!("running {:?}", command);
+		let output = command.output().map_err(Error::JavaExecution)?;
+		parse_macos_java_home(&output)
+	}
+
+	/// Finds a Java home like `JavaHome::find_home()`, then checks that it is at least the provided feature version (such as `17`).
+	///
+	/// # Errors
+	/// This function will error with `Error::VersionTooOld` if the found installation is too old, or in any situation that `JavaHome::find_home()` and `JavaHome::version()` will.
+	pub fn find_home_min(min_feature: u32) -> Result<Self> {
+		JavaHome::find_home()?.require_feature(min_feature)
+	}
+
+	/// Returns this home if it is at least the provided feature version, otherwise `Error::VersionTooOld`.
+	fn require_feature(self, min_feature: u32) -> Result<Self> {
+		let found = self.version()?;
+		if found.major < min_feature {
+			return Err(Error::VersionTooOld { found, required: min_feature });
+		}
+		Ok(self)
+	}
 
+	/// Checks that any existing JAVA_HOME environment variable points to an installation - a directory (see `JavaHome::checked()`) containing a `bin/java` executable - and if not, it falls back to the currently active Java installation's home directory.
+	///
+	/// A valid JAVA_HOME is passed through `JavaHome::normalize()`, so common misconfigurations (such as pointing at the `bin` folder) are corrected.
+	///
+	/// # Errors
+	/// This will error if the directory specified by JAVA_HOME, or its `bin/java`, is unreachable (due to permissions/broken links/etc errors). Also contains error conditions as specified by `JavaHome::find_active_home()`
+	pub fn find_valid_home() -> Result<Self> {
+		JavaHome::valid_env_home()?.map(Ok)
 			// fallback to JavaHome::find_active_home
 			.unwrap_or_else(JavaHome::find_active_home)
 	}
 
-	/// Queries the first found `java` executable on the system path for its home directory.
+	/// Returns the JAVA_HOME environment variable's home if it points to an installation (as with `JavaHome::find_valid_home()`), otherwise validates and returns the provided default (such as a bundled runtime) with `JavaHome::validate()`. Like JAVA_HOME, the default must contain a `bin/java` executable.
 	///
 	/// # Errors
-	/// This function will error if there is an issue finding/running a `java` executable from the path, or if `java -XshowSettings:properties -version` does not return a `java.home` property.
-	pub fn find_active_home() -> Result<Self> {
-		// TODO: Query registry on windows?
-		// https://docs.oracle.com/javase/9/install/installation-jdk-and-jre-microsoft-windows-platforms.htm#JSJIG-GUID-C11500A9-252C-46FE-BB17-FC5A9528EAEB
+	/// This will error if the directory specified by JAVA_HOME is unreachable (due to permissions/broken links/etc errors), or in any situation that `JavaHome::validate()` will for the default.
+	pub fn from_env_or(default: impl Into<PathBuf>) -> Result<Self> {
+		if let Some(home) = JavaHome::valid_env_home()? {
+			return Ok(home);
+		}
 
-		log::debug!("finding currently active JAVA_HOME location by running the `java` command from the system path");
+		let path = default.into();
+		log::debug!("JAVA_HOME not usable, falling back to default of {:?}", path);
+		JavaHome::validate(path)
+	}
 
-		let output = Command::new("java")
-			.arg("-XshowSettings:properties")
-			.arg("-version")
-			.output()
-			.map_err(|e| Error::JavaExecution(e))?;
-
-		let stdout = String::from_utf8_lossy(&output.stdout);
-		let stderr = String::from_utf8_lossy(&output.stderr);
-		let java_home_raw = stdout.lines()
-			.chain(stderr.lines())
-			.find(|line| line.contains("java.home"));
-	
-		match &java_home_raw {
-			Some(l) => log::debug!("\tfound: {}", l),
-			None => log::debug!("\tnot found"),
+	/// Finds a Java installation provided by the `openjdk` package of the active conda (or mamba, etc) environment, as specified by the `CONDA_PREFIX` environment variable.
+	///
+	/// The package's home is `$CONDA_PREFIX/lib/jvm` on Linux/MacOS, or `%CONDA_PREFIX%\Library` on Windows. Older packages placed the installation directly in the environment's prefix, which is checked afterwards. Returns `None` if no environment is active, or it has no Java installation.
+	///
+	/// # Errors
+	/// This will error if a candidate installation's `bin/java` is unreachable (due to permissions/etc errors).
+	pub fn from_conda() -> Result<Option<Self>> {
+		let prefix = match std::env::var_os("CONDA_PREFIX").filter(|var| !var.is_empty()) {
+			Some(prefix) => PathBuf::from(prefix),
+			None => return Ok(None),
 		};
 
-		let java_home = java_home_raw.map(|line| line.find('=').map(|i| line[i+1..].trim()));
+		#[cfg(target_os = "windows")]
+		let package_home = prefix.join("Library");
+		#[cfg(not(target_os = "windows"))]
+		let package_home = prefix.join("lib").join("jvm");
+
+		for candidate in [package_home, prefix] {
+			let home = JavaHome::assume(candidate);
+			log::debug!("looking for a conda java installation at {:?}", home.path);
+			match home.java_executable().metadata() {
+				Ok(meta) if meta.is_file() => return Ok(Some(home)),
+				Err(e) if e.kind() != ErrorKind::NotFound => return Err(Error::IoError(e)),
+				_ => {},
+			}
+		}
+
+		Ok(None)
+	}
 
-		match java_home.flatten() {
-			Some(path) => Ok(JavaHome { path: PathBuf::from(path) }),
-			None => Err(Error::NoJavaHomeProperty),
+	/// Finds every Java installation managed by the `asdf` version manager, in `$ASDF_DATA_DIR/installs/java` (or `~/.asdf/installs/java` if `ASDF_DATA_DIR` is unset).
+	///
+	/// Each installation directory containing a `bin/java` executable (after `JavaHome::normalize()`) is returned, sorted by path. Returns an empty list if asdf has no Java installations.
+	///
+	/// # Errors
+	/// This function will error if the installations directory exists but could not be read.
+	pub fn asdf_installations() -> Result<Vec<JavaHome>> {
+		match asdf_data_dir() {
+			Some(data) => scan_system_root(&data.join("installs").join("java")),
+			None => Ok(Vec::new()),
 		}
 	}
 
-	// (will happily accept PRs for these)
-	
-	// All installations found on path (walk path and get the home dir for all java executables on it)
-	// (query registry if on Windows?)
-	//   HKLM/SOFTWARE/JavaSoft/Java Development Kit/(<= JDK 1.8)/JavaHome
-	//   HKLM/SOFTWARE/JavaSoft/JDK/(>= JDK 1.9)/JavaHome
-	//   HKLM/SOFTWARE/JavaSoft/Java Runtime Environment/(<= JDK 1.8)/JavaHome
-	//   HKLM/SOFTWARE/JavaSoft/JRE/(>= JDK 1.9)/JavaHome
-	// TODO: pub fn installations() -> Result<Vec<PathBuf>>
+	/// Finds the asdf-managed Java installation selected for a directory, as asdf itself would: from the `java` line of the nearest `.tool-versions` file in the directory or its ancestors, falling back to `~/.tool-versions`.
+	///
+	/// The first listed version is used. A `path:` version refers to the installation by path, and `system` versions (deferring to the system's Java) return `None`, as do versions that are not installed.
+	///
+	/// # Errors
+	/// This function will error if a `.tool-versions` file exists but could not be read.
+	pub fn from_asdf_current(dir: &Path) -> Result<Option<Self>> {
+		let home_file = std::env::var_os("HOME").filter(|home| !home.is_empty()).map(|home| PathBuf::from(home).join(".tool-versions"));
+		let candidates = dir.ancestors().map(|dir| dir.join(".tool-versions")).chain(home_file);
+
+		for file in candidates {
+			let contents = match std::fs::read_to_string(&file) {
+				Err(e) if e.kind() == ErrorKind::NotFound => continue,
+				Err(e) => return Err(Error::IoError(e)),
+				Ok(contents) => contents,
+			};
 
-	// jre home
-	// TODO: pub fn jre(&self) -> Result<PathBuf>
-	
-	// jdk home
-	// TODO: pub fn jdk(&self) -> Result<Option<PathBuf>>
+			let version = contents.lines()
+				.map(|line| line.split('#').next().unwrap_or(""))
+				.find_map(|line| {
+					let mut words = line.split_whitespace();
+					if words.next() == Some("java") { words.next() } else { None }
+				});
+			let version = match version {
+				Some(version) => version,
+				None => continue,
+			};
+			log::debug!("asdf selects java version {:?} from {:?}", version, file);
 
-	// binary folder
-	// TODO: pub fn bin(&self) -> Result<PathBuf>
+			let home = match version.strip_prefix("path:") {
+				Some(path) => JavaHome::assume(path),
+				None if version == "system" => return Ok(None),
+				None => match asdf_data_dir() {
+					Some(data) => JavaHome::assume(data.join("installs").join("java").join(version)),
+					None => return Ok(None),
+				},
+			};
+			let home = home.normalize();
+			return Ok(Some(home).filter(|home| home.java_executable().is_file()));
+		}
 
-	/// If the JDK is installed, returns a list of all the include folders necesssary to load JNI/etc headers.
-	#[cfg(feature = "glob")]
-	pub fn include(&self) -> Result<Option<Vec<PathBuf>>> {
-		let base = self.join("include");
-		log::debug!("looking for $JAVA_HOME/include at {:?}", base);
+		Ok(None)
+	}
 
-		match base.metadata() {
-			Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
-			Err(e) => Err(Error::IoError(e)),
-			Ok(meta) if meta.is_dir() => Ok(Some({
-				let escaped = glob::Pattern::escape(base.to_str().ok_or_else(|| Error::PathNotUTF8(base.clone()))?);
-				let pattern = escaped + "/**/*/";
+	/// Finds every Java installation managed by a per-user version manager, which are often not on the path (or in JAVA_HOME) in non-login shells. The following directories are searched:
+	/// * SDKMAN: `$SDKMAN_DIR/candidates/java` (or `~/.sdkman/candidates/java`)
+	/// * jabba: `$JABBA_HOME/jdk` (or `~/.jabba/jdk`)
+	/// * asdf: see `JavaHome::asdf_installations()`
+	///
+	/// Each installation directory containing a `bin/java` executable (after `JavaHome::normalize()`) is returned, in the above order and then by path. Version managers that are not installed are skipped, and installations are deduplicated by their canonicalized path (such as SDKMAN's `current` link).
+	///
+	/// # Errors
+	/// This function will error if an installations directory exists but could not be read.
+	pub fn installations_from_version_managers() -> Result<Vec<JavaHome>> {
+		let in_home = |var: &str, default: &str| {
+			std::env::var_os(var)
+				.filter(|dir| !dir.is_empty())
+				.map(PathBuf::from)
+				.or_else(|| user_home().map(|home| home.join(default)))
+		};
+		let roots = [
+			in_home("SDKMAN_DIR", ".sdkman").map(|dir| dir.join("candidates").join("java")),
+			in_home("JABBA_HOME", ".jabba").map(|dir| dir.join("jdk")),
+			asdf_data_dir().map(|dir| dir.join("installs").join("java")),
+		];
 
-				std::iter::once(Ok(base.clone())) // include base dir
-					.chain(glob::glob(&pattern).unwrap()) // platform dependent dirs
-					.collect::<std::result::Result<Vec<PathBuf>, glob::GlobError>>()? // collect + bubble errors
-			})),
-			Ok(_) => Err(Error::BadJavaHomePath(self.path.clone())),
+		let mut seen = std::collections::HashSet::new();
+		let mut homes = Vec::new();
+		for root in roots.iter().flatten() {
+			for home in scan_system_root(root)? {
+				if seen.insert(home.canonicalize().map_or_else(|_| home.path.clone(), |canonical| canonical.path)) {
+					homes.push(home);
+				}
+			}
 		}
+		Ok(homes)
 	}
 
-	/// The path to the JVM's platform-specific native library, suitable for linking with. (`jvm.dll`/`libjvm.so`/`libjli.dylib`)
-	#[cfg(feature = "glob")]
-	pub fn native_library(&self) -> Result<PathBuf> {
-		let base = &self.path;
-		let escaped = glob::Pattern::escape(base.to_str().ok_or_else(|| Error::PathNotUTF8(base.clone()))?);
-		let pattern = escaped + "/**/" + NATIVE_LIBRARY_FILENAME;
-		log::debug!("looking for JVM native library with glob {:?}", pattern);
-		// developer note: if on linux, LD_LIBRARY_PATH may need to be set for the system loader to find it
-		// alternatively, is there a way to tell cargo to use the absolute path?
+	/// Returns the JAVA_HOME environment variable's home if it points to an existing directory containing a `bin/java` executable, normalized with `JavaHome::normalize()`.
+	pub(crate) fn valid_env_home() -> Result<Option<Self>> {
+		Ok(JavaHome::env_home()?.ok())
+	}
 
-		Ok(glob::glob(&pattern)
-			.unwrap() // pattern should always be valid
-			.next().ok_or(Error::NoNativeLibrary)??)
+	/// Like `JavaHome::valid_env_home()`, but returns why the JAVA_HOME environment variable was not usable.
+	fn env_home() -> Result<std::result::Result<Self, FallbackReason>> {
+		let path = match std::env::var_os(JavaHome::ENV_VAR).filter(|var| !var.is_empty()) {
+			Some(path) => PathBuf::from(path),
+			None => return Ok(Err(FallbackReason::EnvVarUnset)),
+		};
+		JavaHome::env_home_at(path)
 	}
 
-	/// A convience function to search for a specific file within the home directory. Matches the filename literally.
-	#[cfg(feature = "glob")]
-	pub fn find_file(&self, file: &str) -> Result<Option<PathBuf>> {
-		let base = &self.path;
-		let base_escaped = glob::Pattern::escape(base.to_str().ok_or_else(|| Error::PathNotUTF8(base.clone()))?);
-		let file_escaped = glob::Pattern::escape(file);
-		let pattern = base_escaped + "/**/" + &file_escaped;
-		glob::glob(&pattern)
-			.unwrap() // pattern should always be valid
-			.next()
-			.transpose()
-			.map_err(Error::GlobError)
+	/// Like `JavaHome::env_home()`, for the path JAVA_HOME was set to.
+	fn env_home_at(path: PathBuf) -> Result<std::result::Result<Self, FallbackReason>> {
+		match JavaHome::checked(path) {
+			// It's not a directory? outdated env var? Let the caller pick another source
+			Err(Error::BadJavaHomePath(path)) => {
+				// metadata() follows links, so a dangling one looks like any other missing path (unlike a live link to a file)
+				let dangling = path.symlink_metadata().is_ok_and(|meta| meta.file_type().is_symlink())
+					&& path.metadata().is_err_and(|e| e.kind() == ErrorKind::NotFound);
+				Ok(Err(if dangling { FallbackReason::EnvVarBrokenSymlink(path) } else { FallbackReason::EnvVarBadPath(path) }))
+			},
+
+			// bubble up IO (permission/etc) errors
+			Err(e) => Err(e),
+
+			// a lingering directory of an uninstalled JDK, or a parent of the installation
+			Ok(home) => match home.java_executable().metadata() {
+				Ok(meta) if meta.is_file() => Ok(Ok(home)),
+				Err(e) if e.kind() != ErrorKind::NotFound => Err(Error::IoError(e)),
+				_ => Ok(Err(FallbackReason::EnvVarNoJava(home.path))),
+			},
+		}
 	}
 
-	/// A convience function to search for a specific folder within the home directory. Matches the folder name literally.
-	#[cfg(feature = "glob")]
-	pub fn find_folder(&self, folder: &str) -> Result<Option<PathBuf>> {
-		let base = &self.path;
-		let base_escaped = glob::Pattern::escape(base.to_str().ok_or_else(|| Error::PathNotUTF8(base.clone()))?);
-		let fold_escaped = glob::Pattern::escape(folder);
-		let pattern = base_escaped + "/**/" + &fold_escaped + "/";
-		glob::glob(&pattern)
-			.unwrap() // pattern should always be valid
-			.next()
-			.transpose()
-			.map_err(Error::GlobError)
+	/// Like `JavaHome::find_valid_home()`, but also returns why the JAVA_HOME environment variable was passed over, if the active Java installation was used instead.
+	///
+	/// # Errors
+	/// This function will error in any situation that `JavaHome::find_valid_home()` will.
+	pub fn find_valid_home_verbose() -> Result<(Self, Option<FallbackReason>)> {
+		match JavaHome::env_home()? {
+			Ok(home) => Ok((home, None)),
+			Err(reason) => {
+				log::debug!("JAVA_HOME not usable ({}), falling back to the active java installation", reason);
+				Ok((JavaHome::find_active_home()?, Some(reason)))
+			},
+		}
 	}
-}
-impl Deref for JavaHome {
-	type Target = Path;
-	fn deref(&self) -> &Self::Target {
-		&self.path
+
+	/// Queries the first found `java` executable on the system path for its home directory.
+	///
+	/// The executable is given `JavaHome::DEFAULT_TIMEOUT` to respond, see `JavaHome::find_active_home_timeout()`.
+	///
+	/// # Errors
+	/// This function will error with `Error::JavaNotFound` if there is no `java` executable on the path, with `Error::JavaExecution` if it could not be run, or if `java -XshowSettings:properties -version` does not return a `java.home` property. If it returns no output at all, `Error::EmptyProbeOutput` is returned instead, if it is too old to support `-XshowSettings` (before Java 7), `Error::ShowSettingsUnsupported`, and if it does not exit in time, `Error::JavaTimeout`.
+	pub fn find_active_home() -> Result<Self> {
+		JavaHome::find_active_home_timeout(JavaHome::DEFAULT_TIMEOUT)
+	}
+
+	/// Queries the first found `java` executable on the system path for its home directory, like `JavaHome::find_active_home()`, killing it if it does not exit within the timeout. This guards against `java` wrappers that hang (such as on an unreachable network mount).
+	///
+	/// # Errors
+	/// This function will error with `Error::JavaTimeout` if the executable does not exit in time, or in any other situation that `JavaHome::find_active_home()` will.
+	pub fn find_active_home_timeout(timeout: Duration) -> Result<Self> {
+		log::debug!("finding currently active JAVA_HOME location by running the `java` command from the system path");
+		JavaHome::query_home_within("java", Some(timeout))
+	}
+
+	/// Like `JavaHome::find_home()`, but remembers the first home found, so later calls return it without spawning `java` again.
+	///
+	/// Only successful results are remembered - a failed search is retried on the next call. Call `JavaHome::clear_cache()` to search again, such as after changing JAVA_HOME.
+	///
+	/// # Errors
+	/// This function will error in any situation that `JavaHome::find_home()` will.
+	pub fn find_home_cached() -> Result<Self> {
+		cached(&CACHED_HOME, JavaHome::find_home)
+	}
+
+	/// Like `JavaHome::find_active_home()`, but remembers the first home found, as with `JavaHome::find_home_cached()`.
+	///
+	/// # Errors
+	/// This function will error in any situation that `JavaHome::find_active_home()` will.
+	pub fn find_active_home_cached() -> Result<Self> {
+		cached(&CACHED_ACTIVE_HOME, JavaHome::find_active_home)
+	}
+
+	/// Forgets the homes remembered by `JavaHome::find_home_cached()` and `JavaHome::find_active_home_cached()`, so their next calls search again.
+	pub fn clear_cache() {
+		for cache in [&CACHED_HOME, &CACHED_ACTIVE_HOME] {
+			*cache.lock().unwrap_or_else(PoisonError::into_inner) = None;
+		}
+	}
+
+	/// Like `JavaHome::find_active_home()`, but also returns the `java` executable on the system path that reported the home (as `which java` would resolve it), such as to run further commands with the same runtime.
+	///
+	/// The executable's path is returned as found on the path, without resolving symlinks - these commonly point into the installation itself (as with `/usr/bin/java` through `update-alternatives`).
+	///
+	/// # Errors
+	/// This function will error in any situation that `JavaHome::find_active_home()` will.
+	pub fn find_active_home_detailed() -> Result<(Self, PathBuf)> {
+		let java = search_path("java").ok_or(Error::JavaNotFound)?;
+		log::debug!("finding the JAVA_HOME location of {:?}, the first java executable on the system path", java);
+		let home = JavaHome::query_home_within(&java, Some(JavaHome::DEFAULT_TIMEOUT))?;
+		Ok((home, java))
+	}
+
+	/// Queries the provided `java` executable (such as `/opt/graalvm/bin/java`) for its home directory, like `JavaHome::find_active_home()` does for the one on the system path. This interrogates a specific JVM without changing `PATH`.
+	///
+	/// The executable is given `JavaHome::DEFAULT_TIMEOUT` to respond.
+	///
+	/// # Errors
+	/// This function will error in any situation that `JavaHome::find_active_home()` will, for the provided executable.
+	pub fn find_active_home_from(java_exe: impl AsRef<Path>) -> Result<Self> {
+		log::debug!("finding the JAVA_HOME location of {:?}", java_exe.as_ref());
+		JavaHome::query_home_within(java_exe.as_ref(), Some(JavaHome::DEFAULT_TIMEOUT))
+	}
+
+	/// Queries the provided `java` executable for its home directory like `JavaHome::find_active_home_from()`, running it with a controlled environment: cleared first if `clear` is set, then with each of the provided variables set.
+	///
+	/// This keeps variables such as `_JAVA_OPTIONS` and `JAVA_TOOL_OPTIONS` (see `JavaHome::interfering_env_vars()`) from affecting the probe. Note that clearing the environment removes `PATH` (and on Windows, `SystemRoot`), which some `java` launchers and wrappers need - pass them back in if so.
+	///
+	/// # Errors
+	/// This function will error in any situation that `JavaHome::find_active_home()` will, for the provided executable.
+	pub fn find_active_home_with_env(java_exe: impl AsRef<Path>, env: &[(OsString, OsString)], clear: bool) -> Result<Self> {
+		let mut command = Command::new(java_exe.as_ref());
+		command.args(["-XshowSettings:properties", "-version"]);
+		if clear {
+			command.env_clear();
+		}
+		command.envs(env.iter().map(|(key, value)| (key, value)));
+		log::debug!("finding the JAVA_HOME location of {:?} with a controlled environment", java_exe.as_ref());
+
+		let output = run_command_within(command, Some(JavaHome::DEFAULT_TIMEOUT))?;
+		parse_java_home(&output).map(JavaHome::assume)
+	}
+
+	/// Like `JavaHome::find_home()`, but reads `JAVA_HOME` and `PATH` from the provided environment rather than this process's. This avoids racing on the process environment when resolving homes for several environments at once.
+	///
+	/// Variable names are matched case-insensitively on Windows, as they are by the OS. Like `JavaHome::find_active_home()`, a `java` executable found on the provided `PATH` is given `JavaHome::DEFAULT_TIMEOUT` to respond.
+	///
+	/// # Errors
+	/// This function will error with `Error::JavaNotFound` if `JAVA_HOME` is unset and no `java` executable is on the provided `PATH`, or in any other situation that `JavaHome::find_active_home()` will.
+	pub fn find_home_in_env(envs: &HashMap<OsString, OsString>) -> Result<Self> {
+		let get = |name: &str| {
+			envs.get(OsStr::new(name))
+				.or_else(|| if cfg!(windows) {
+					envs.iter().find(|(key, _)| key.eq_ignore_ascii_case(name)).map(|(_, value)| value)
+				} else {
+					None
+				})
+				.filter(|value| !value.is_empty())
+		};
+
+		if let Some(home) = get(JavaHome::ENV_VAR) {
+			return Ok(JavaHome::assume(home));
+		}
+
+		let java = get("PATH")
+			.and_then(|path| search_path_in(path, "java"))
+			.ok_or(Error::JavaNotFound)?;
+		log::debug!("querying java executable from the provided PATH at {:?}", java);
+		JavaHome::query_home_within(java, Some(JavaHome::DEFAULT_TIMEOUT))
+	}
+
+	/// Runs the provided `java` executable, returning the home directory it reports. It is killed with `Error::JavaTimeout` if it does not finish within the timeout.
+	fn query_home_within(java: impl AsRef<OsStr>, timeout: Option<Duration>) -> Result<Self> {
+		let output = run_combined_within(java, &["-XshowSettings:properties", "-version"], timeout)?;
+		parse_java_home(&output).map(JavaHome::assume)
+	}
+
+	/// If this path is the `bin` directory of an installation (it is named `bin` and contains a `java` executable), returns the installation's actual home directory.
+	pub fn parent_of_bin(&self) -> Option<JavaHome> {
+		if self.path.file_name()? != "bin" || !self.path.join(executable_name("java")).is_file() {
+			return None;
+		}
+		self.path.parent().map(JavaHome::assume)
+	}
+
+	/// Corrects common misconfigurations of a Java home path, returning the installation's actual home directory.
+	///
+	/// The following cases are corrected:
+	/// * A path to an installation's `bin` folder (`/opt/jdk/bin` becomes `/opt/jdk`)
+	/// * A path to an installation's `java` executable (`/opt/jdk/bin/java` becomes `/opt/jdk`)
+	/// * A path to the nested `jre` folder of a legacy (JDK 8 and earlier) installation, where the parent is the home of a JDK (`/opt/jdk8/jre` becomes `/opt/jdk8`)
+	/// * A path to a MacOS bundle, rather than the home within it (`/Library/Java/JavaVirtualMachines/jdk.jdk` becomes `/Library/Java/JavaVirtualMachines/jdk.jdk/Contents/Home`)
+	///
+	/// Any other path is returned unchanged. This function only inspects the filesystem, and does not otherwise validate the home.
+	pub fn normalize(&self) -> JavaHome {
+		if let Some(home) = self.parent_of_bin() {
+			return home;
+		}
+
+		if self.path.file_name() == Some(executable_name("java").as_ref()) && self.path.is_file() {
+			if let Some(home) = self.path.parent().and_then(|bin| JavaHome::assume(bin).parent_of_bin()) {
+				return home;
+			}
+		}
+
+		if let Some(jdk) = self.enclosing_jdk() {
+			return jdk;
+		}
+
+		let bundle_home = self.path.join("Contents").join("Home");
+		if bundle_home.is_dir() {
+			return JavaHome::assume(bundle_home);
+		}
+
+		self.clone()
+	}
+
+	/// If this path is the nested `jre` folder of a legacy (JDK 8 and earlier) installation, returns the home of the JDK containing it.
+	fn enclosing_jdk(&self) -> Option<JavaHome> {
+		if self.path.file_name() != Some("jre".as_ref()) {
+			return None;
+		}
+		self.path.parent()
+			.filter(|parent| parent.join("bin").join(executable_name("java")).is_file())
+			.map(JavaHome::assume)
+	}
+
+	/// Queries this installation's JVM for the final value of each of its `-XX` flags, as reported by `java -XX:+PrintFlagsFinal -version`.
+	///
+	/// These reflect the JVM's ergonomic defaults for the current machine, such as `MaxHeapSize` or the selected garbage collector (`UseG1GC`, `UseParallelGC`, etc). Flags without a value (such as unset `ccstr` flags) map to an empty string.
+	///
+	/// Note that this starts a full JVM and prints several hundred flags, so it is a considerably heavier probe than reading system properties. Callers needing several flags should call this once and keep the map.
+	///
+	/// # Errors
+	/// This function will error if this home's `bin/java` executable could not be run, with `Error::JavaTimeout` if it does not exit within `JavaHome::DEFAULT_TIMEOUT`, or with `Error::NoFlagsInformation` if it did not print a flag table (such as when it rejects the option).
+	pub fn default_flags(&self) -> Result<HashMap<String, String>> {
+		let java = self.java_executable();
+		log::debug!("querying final JVM flags from {:?}", java);
+
+		let output = run_combined_within(&java, &["-XX:+PrintFlagsFinal", "-version"], Some(JavaHome::DEFAULT_TIMEOUT))?;
+		let flags = parse_flags_final(&output);
+		if flags.is_empty() {
+			log::debug!("	no flags found in output: {:?}", output);
+			return Err(Error::NoFlagsInformation);
+		}
+		Ok(flags)
+	}
+
+	/// The path to this home's `java` executable. It is not checked for existence.
+	fn java_executable(&self) -> PathBuf {
+		self.path.join("bin").join(executable_name("java"))
+	}
+
+	/// Reads the `release` file at the root of this home, which describes the installation with `KEY="value"` lines (`JAVA_VERSION`, `IMPLEMENTOR`, `OS_ARCH`, etc).
+	///
+	/// Returns `None` if the installation has no `release` file, as is the case for some JDK 8 and earlier distributions.
+	///
+	/// # Errors
+	/// This function will error if the `release` file exists but could not be read.
+	pub fn release(&self) -> Result<Option<HashMap<String, String>>> {
+		let path = self.join("release");
+		log::debug!("reading installation release file at {:?}", path);
+
+		match std::fs::read_to_string(&path) {
+			Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+			Err(e) => Err(Error::IoError(e)),
+			Ok(contents) => Ok(Some(parse_release(&contents))),
+		}
+	}
+
+	/// Checks if this installation is an early-access (pre-release) build, such as `21-ea`.
+	///
+	/// The version is read from the `release` file's `JAVA_VERSION` if present, otherwise from the banner printed by `java -version`.
+	///
+	/// # Errors
+	/// This function will error if the `release` file is unreadable, or if it is absent and this home's `bin/java` could not be run or did not report a version.
+	pub fn is_early_access(&self) -> Result<bool> {
+		let version = self.raw_version()?;
+		Ok(matches!(version.split_once('-'), Some((_, pre)) if pre.starts_with("ea")))
+	}
+
+	/// Determines the version of this installation.
+	///
+	/// The version is read from the `release` file's `JAVA_VERSION` if present, otherwise from the banner printed by `java -version`.
+	///
+	/// # Errors
+	/// This function will error if the `release` file is unreadable, or if it is absent and this home's `bin/java` could not be run or did not report a version. A version that could not be understood results in `Error::VersionParse`.
+	pub fn version(&self) -> Result<JavaVersion> {
+		self.raw_version()?.parse()
+	}
+
+	/// Checks if this installation supports virtual threads as a stable feature (Java 21 and later).
+	///
+	/// Java 19 and 20 have virtual threads only as a preview feature, for which this returns `false` - see `JavaHome::preview_args()` for the arguments needed to use them there.
+	///
+	/// # Errors
+	/// This function will error in any situation that `JavaHome::version()` will.
+	pub fn supports_virtual_threads(&self) -> Result<bool> {
+		Ok(self.version()?.major >= 21)
+	}
+
+	/// Returns the extra `java` arguments needed to use virtual threads on this installation: `--enable-preview` on Java 19 and 20, where they are a preview feature, and none otherwise.
+	///
+	/// # Errors
+	/// This function will error in any situation that `JavaHome::version()` will.
+	pub fn preview_args(&self) -> Result<Vec<&'static str>> {
+		match self.version()?.major {
+			19 | 20 => Ok(vec!["--enable-preview"]),
+			_ => Ok(Vec::new()),
+		}
+	}
+
+	/// Returns the exact `java.version` property reported by this installation's JVM, such as `17.0.8` or `1.8.0_292`.
+	///
+	/// # Errors
+	/// This function will error if this home's `bin/java` executable could not be run, or with `Error::NoVersionInformation` if it did not report a `java.version` property.
+	pub fn version_string(&self) -> Result<String> {
+		self.properties_of()?
+			.remove("java.version")
+			.ok_or(Error::NoVersionInformation)
+	}
+
+	/// Returns the vendor of this installation's JVM, from the `java.vendor` property (such as `Eclipse Adoptium`, `Amazon.com Inc.`, or `Oracle Corporation`).
+	///
+	/// The string is returned as-is, as vendors are not consistent in their naming. Note that several distributions report a generic vendor here, see `JavaHome::vendor_version()` for the distribution's own name.
+	///
+	/// # Errors
+	/// This function will error if this home's `bin/java` executable could not be run, or with `Error::NoVendorInformation` if it did not report a `java.vendor` property.
+	pub fn vendor(&self) -> Result<String> {
+		self.properties_of()?
+			.remove("java.vendor")
+			.ok_or(Error::NoVendorInformation)
+	}
+
+	/// Returns the distribution's version string from the `java.vendor.version` property (such as `Temurin-17.0.8+7` or `Oracle GraalVM 21+35.1`), or `None` if the JVM does not report one. The property was added in Java 10, and is not reported by every distribution.
+	///
+	/// # Errors
+	/// This function will error if this home's `bin/java` executable could not be run.
+	pub fn vendor_version(&self) -> Result<Option<String>> {
+		Ok(self.properties_of()?.remove("java.vendor.version"))
+	}
+
+	/// Checks if this installation is a GraalVM distribution.
+	///
+	/// This is determined from the cheapest markers first: a `bin/native-image` executable, then `GRAALVM_VERSION` or a `GraalVM` implementor in the `release` file, and finally a `GraalVM` substring in the `java.vendor.version` or `java.vm.name` properties. Note that older GraalVM releases install `native-image` separately, so a GraalVM installation may lack it - check for `bin/native-image` itself before relying on it. Any errors while checking are treated as the marker being absent.
+	pub fn is_graalvm(&self) -> bool {
+		// native-image is a batch script on Windows
+		let bin = self.path.join("bin");
+		if bin.join(executable_name("native-image")).is_file() || bin.join("native-image.cmd").is_file() {
+			return true;
+		}
+
+		if let Ok(Some(release)) = self.release() {
+			let implementor = release.get("IMPLEMENTOR").is_some_and(|implementor| implementor.contains("GraalVM"));
+			if implementor || release.contains_key("GRAALVM_VERSION") {
+				return true;
+			}
+		}
+
+		self.properties_of().is_ok_and(|props| {
+			["java.vendor.version", "java.vm.name"].iter()
+				.any(|key| props.get(*key).is_some_and(|value| value.contains("GraalVM")))
+		})
+	}
+
+	/// Returns the name of the default charset this installation's JVM uses (such as `UTF-8` or `Cp1252`), from the `file.encoding` property, or `sun.jnu.encoding` if that is absent.
+	///
+	/// Note that since Java 18 `file.encoding` defaults to `UTF-8` everywhere, while older JVMs use the platform's encoding (often a legacy codepage on Windows).
+	///
+	/// # Errors
+	/// This function will error if this home's `bin/java` executable could not be run, or with `Error::NoEncodingInformation` if it reported neither property.
+	pub fn file_encoding(&self) -> Result<String> {
+		let mut props = self.properties_of()?;
+		props.remove("file.encoding")
+			.or_else(|| props.remove("sun.jnu.encoding"))
+			.ok_or(Error::NoEncodingInformation)
+	}
+
+	/// Returns the class file version produced by this installation's JVM as a `(major, minor)` pair, from the `java.class.version` property (`61.0` becomes `(61, 0)`).
+	///
+	/// # Errors
+	/// This function will error if this home's `bin/java` executable could not be run, with `Error::NoVersionInformation` if it did not report a `java.class.version` property, or with `Error::VersionParse` if the property could not be understood.
+	pub fn class_version(&self) -> Result<(u16, u16)> {
+		let raw = self.properties_of()?
+			.remove("java.class.version")
+			.ok_or(Error::NoVersionInformation)?;
+
+		let (major, minor) = raw.split_once('.').unwrap_or((&raw, "0"));
+		match (major.parse(), minor.parse()) {
+			(Ok(major), Ok(minor)) => Ok((major, minor)),
+			_ => Err(Error::VersionParse(raw)),
+		}
+	}
+
+	/// Gathers this installation's version, vendor, architecture, kind, and native library location at once, running the JVM a single time.
+	///
+	/// # Errors
+	/// This function will error if this home's `bin/java` executable could not be run, or with `Error::NoVersionInformation` or `Error::NoArchitectureInformation` if it did not report a `java.version` or `os.arch` property. A version that could not be understood results in `Error::VersionParse`.
+	pub fn summary(&self) -> Result<JavaSummary> {
+		let mut props = self.properties_of()?;
+		let version = props.remove("java.version").ok_or(Error::NoVersionInformation)?.parse()?;
+		let arch = props.remove("os.arch").ok_or(Error::NoArchitectureInformation)?;
+
+		Ok(JavaSummary {
+			path: self.path.clone(),
+			version,
+			vendor: props.remove("java.vendor"),
+			arch,
+			is_jdk: self.is_jdk(),
+			#[cfg(feature = "glob")]
+			native_library: self.native_library()
+				.ok()
+				.and_then(|lib| lib.strip_prefix(&self.path).ok().map(PathBuf::from)),
+		})
+	}
+
+	/// Determines the processor architecture this installation was built for, such as to check that its native library can be loaded into the calling process before linking to it.
+	///
+	/// The architecture is read from the `release` file's `OS_ARCH` if present, otherwise from the `os.arch` property reported by this home's `bin/java`.
+	///
+	/// # Errors
+	/// This function will error if the `release` file is unreadable, or if it is absent and this home's `bin/java` could not be run or did not report its architecture (`Error::NoArchitectureInformation`).
+	pub fn arch(&self) -> Result<JvmArch> {
+		self.raw_arch().map(|arch| JvmArch::from_os_arch(&arch))
+	}
+
+	/// Checks if this installation's JVM is a 64-bit VM.
+	///
+	/// This is determined from the `sun.arch.data.model` property (`64` or `32`) when available. Otherwise, such as on minimal runtimes where the properties probe fails, the VM line of the `java -version` banner is checked for `64-Bit` (as in `OpenJDK 64-Bit Server VM`).
+	///
+	/// # Errors
+	/// This function will error if this home's `bin/java` executable could not be run, or with `Error::NoArchitectureInformation` if neither the properties nor the banner reveal the VM's bitness.
+	pub fn is_64bit(&self) -> Result<bool> {
+		match self.properties_of().map(|mut props| props.remove("sun.arch.data.model")) {
+			Ok(Some(model)) if model == "64" => return Ok(true),
+			Ok(Some(model)) if model == "32" => return Ok(false),
+			Ok(model) => log::debug!("unusable sun.arch.data.model property ({:?}), checking version banner", model),
+			Err(e) => log::debug!("properties probe failed ({}), checking version banner", e),
+		}
+
+		let banner = self.version_banner()?;
+		banner.lines()
+			.find(|line| line.contains(" VM "))
+			.map(|line| line.contains("64-Bit"))
+			.ok_or(Error::NoArchitectureInformation)
+	}
+
+	/// Checks if this installation would run under Rosetta 2 translation - that it is an x86_64 installation, on an Apple Silicon (arm64) Mac.
+	///
+	/// Translated JVMs run noticeably slower, and their native library cannot be loaded into native arm64 processes. The host is checked with `sysctl hw.optional.arm64`, so this is accurate even when the calling process is itself translated.
+	///
+	/// # Errors
+	/// This function will error if the `release` file is unreadable, or if it is absent and this home's `bin/java` could not be run or did not report its architecture.
+	#[cfg(target_os = "macos")]
+	pub fn runs_under_rosetta(&self) -> Result<bool> {
+		let arch = self.raw_arch()?;
+		if arch != "x86_64" && arch != "amd64" {
+			return Ok(false);
+		}
+
+		// the key is absent on Intel Macs, making sysctl fail
+		let host_is_arm64 = Command::new("sysctl")
+			.arg("-n")
+			.arg("hw.optional.arm64")
+			.output()
+			.is_ok_and(|output| output.status.success() && String::from_utf8_lossy(&output.stdout).trim() == "1");
+		Ok(host_is_arm64)
+	}
+
+	/// Checks if this installation's `java` executable has a valid code signature - verified with `codesign --verify` on MacOS, or as an Authenticode signature with PowerShell's `Get-AuthenticodeSignature` on Windows.
+	///
+	/// Unsigned executables, and those whose signatures are invalid (such as after being modified), are reported as `false`.
+	///
+	/// # Errors
+	/// This function will error with `Error::SignatureToolFailed` if the verification tool could not be run.
+	#[cfg(any(target_os = "macos", target_os = "windows"))]
+	pub fn is_code_signed(&self) -> Result<bool> {
+		let java = self.java_executable();
+
+		#[cfg(target_os = "macos")]
+		let status = Command::new("codesign")
+			.arg("--verify")
+			.arg(&java)
+			.output()
+			.map_err(Error::SignatureToolFailed)
+			.map(|output| output.status.success());
+
+		#[cfg(target_os = "windows")]
+		let status = authenticode_query(&java, "Status").map(|status| status == "Valid");
+
+		status
+	}
+
+	/// Returns the identity that signed this installation's `java` executable, such as `Developer ID Application: Oracle America, Inc. (VB5E2TV963)` on MacOS (the leaf `Authority` reported by `codesign`) or the subject of the signing certificate on Windows. Returns `None` if the executable is not signed.
+	///
+	/// This does not check that the signature is valid, see `JavaHome::is_code_signed()`.
+	///
+	/// # Errors
+	/// This function will error with `Error::SignatureToolFailed` if the verification tool could not be run.
+	#[cfg(any(target_os = "macos", target_os = "windows"))]
+	pub fn code_signer(&self) -> Result<Option<String>> {
+		let java = self.java_executable();
+
+		#[cfg(target_os = "macos")]
+		let signer = Command::new("codesign")
+			.arg("--display")
+			.arg("--verbose=2")
+			.arg(&java)
+			.output()
+			.map_err(Error::SignatureToolFailed)
+			.map(|output| {
+				// the details are printed to stderr, leaf certificate first
+				String::from_utf8_lossy(&output.stderr).lines()
+					.find_map(|line| line.strip_prefix("Authority="))
+					.map(String::from)
+			});
+
+		#[cfg(target_os = "windows")]
+		let signer = authenticode_query(&java, "SignerCertificate.Subject").map(|subject| Some(subject).filter(|subject| !subject.is_empty()));
+
+		signer
+	}
+
+	/// Returns the unparsed architecture from the `release` file's `OS_ARCH`, or the `os.arch` property if there is no `release` file.
+	fn raw_arch(&self) -> Result<String> {
+		if let Some(arch) = self.release()?.and_then(|mut release| release.remove("OS_ARCH")) {
+			return Ok(arch);
+		}
+
+		self.properties_of()?
+			.remove("os.arch")
+			.ok_or(Error::NoArchitectureInformation)
+	}
+
+	/// Returns the unparsed version from the `release` file, or the `java -version` banner if there is no `release` file.
+	fn raw_version(&self) -> Result<String> {
+		if let Some(version) = self.release()?.and_then(|mut release| release.remove("JAVA_VERSION")) {
+			return Ok(version);
+		}
+
+		let banner = self.version_banner()?;
+		banner_version(&banner)
+			.map(String::from)
+			.ok_or(Error::NoVersionInformation)
+	}
+
+	/// Determines the C standard library (glibc or musl) this installation was built against. A JVM built against one generally does not run on a system using the other.
+	///
+	/// The `LIBC` entry of the `release` file is used if present (JDK 16 and later), otherwise the dynamic dependencies of the native library are inspected (requires the `glob` feature). Returns `None` if neither gives an answer.
+	///
+	/// # Errors
+	/// This function will error if the `release` file or the native library exist but could not be read.
+	#[cfg(target_os = "linux")]
+	pub fn libc_kind(&self) -> Result<Option<LibcKind>> {
+		if let Some(libc) = self.release()?.and_then(|mut release| release.remove("LIBC")) {
+			log::debug!("found LIBC={:?} in release file", libc);
+			match libc.as_str() {
+				"gnu" => return Ok(Some(LibcKind::Glibc)),
+				"musl" => return Ok(Some(LibcKind::Musl)),
+				_ => {},
+			}
+		}
+
+		#[cfg(feature = "glob")]
+		{
+			let library = match self.native_library() {
+				Ok(library) => library,
+				Err(Error::NoNativeLibrary) => return Ok(None),
+				Err(e) => return Err(e),
+			};
+			let data = std::fs::read(&library).map_err(Error::IoError)?;
+			let needed = elf::needed_libraries(&data).unwrap_or_default();
+			log::debug!("native library {:?} depends on {:?}", library, needed);
+
+			for lib in needed {
+				if lib.starts_with("libc.musl") || lib.starts_with("ld-musl") {
+					return Ok(Some(LibcKind::Musl));
+				} else if lib.starts_with("libc.so.") {
+					return Ok(Some(LibcKind::Glibc));
+				}
+			}
+		}
+
+		Ok(None)
+	}
+
+	/// Lists every executable tool in this installation's `bin` directory (`java`, `javac`, `jar`, etc), sorted by path.
+	///
+	/// On Unix platforms, files with any executable permission bit are included. On Windows, files with an `.exe` extension are included.
+	///
+	/// # Errors
+	/// This function will error with `Error::BadJavaHomePath` if there is no `bin` directory, or if it could not be read.
+	pub fn bin_tools(&self) -> Result<Vec<PathBuf>> {
+		let bin = self.join("bin");
+		let entries = match std::fs::read_dir(&bin) {
+			Err(e) if e.kind() == ErrorKind::NotFound => return Err(Error::BadJavaHomePath(self.path.clone())),
+			Err(e) => return Err(Error::IoError(e)),
+			Ok(entries) => entries,
+		};
+
+		let mut tools = Vec::new();
+		for entry in entries {
+			let path = entry.map_err(Error::IoError)?.path();
+			// follow symlinks, as some distributions link their tools elsewhere
+			let meta = match path.metadata() {
+				Ok(meta) => meta,
+				Err(e) if e.kind() == ErrorKind::NotFound => continue, // dangling link
+				Err(e) => return Err(Error::IoError(e)),
+			};
+			if meta.is_file() && is_executable(&path, &meta) {
+				tools.push(path);
+			}
+		}
+		tools.sort();
+
+		Ok(tools)
+	}
+
+	/// Locates the `jspawnhelper` executable, which the JVM uses to launch subprocesses (`Process.start`, etc) on Unix platforms. A missing or non-executable helper is a common cause of subprocess launch failures.
+	///
+	/// The helper is at `lib/jspawnhelper` since Java 9, and within an architecture folder (such as `jre/lib/amd64/jspawnhelper`) previously. Returns `None` if it could not be found.
+	///
+	/// # Errors
+	/// This function will error if a candidate location exists but could not be accessed.
+	#[cfg(unix)]
+	pub fn jspawnhelper(&self) -> Result<Option<PathBuf>> {
+		const HELPER: &str = "jspawnhelper";
+
+		for lib in [self.path.join("lib"), self.path.join("jre").join("lib")] {
+			let entries = match std::fs::read_dir(&lib) {
+				Err(e) if e.kind() == ErrorKind::NotFound => continue,
+				Err(e) => return Err(Error::IoError(e)),
+				Ok(entries) => entries,
+			};
+
+			let direct = lib.join(HELPER);
+			if direct.is_file() {
+				return Ok(Some(direct));
+			}
+
+			let mut arch_dirs = entries
+				.map(|entry| entry.map(|entry| entry.path()))
+				.collect::<std::io::Result<Vec<_>>>()
+				.map_err(Error::IoError)?;
+			arch_dirs.sort();
+			if let Some(nested) = arch_dirs.into_iter().map(|dir| dir.join(HELPER)).find(|helper| helper.is_file()) {
+				return Ok(Some(nested));
+			}
+		}
+
+		Ok(None)
+	}
+
+	/// Checks that `JavaHome::jspawnhelper()` finds the helper, and that it has an executable permission bit.
+	///
+	/// # Errors
+	/// This function will error in any situation that `JavaHome::jspawnhelper()` will, or if the helper's permissions could not be read.
+	#[cfg(unix)]
+	pub fn jspawnhelper_is_executable(&self) -> Result<bool> {
+		match self.jspawnhelper()? {
+			Some(helper) => {
+				let meta = helper.metadata().map_err(Error::IoError)?;
+				Ok(is_executable(&helper, &meta))
+			},
+			None => Ok(false),
+		}
+	}
+
+	/// Locates a tool (such as `javac`) by name, first within this installation's `bin` directory, then by searching the system path.
+	///
+	/// The platform's executable suffix (`.exe` on Windows) is appended to the name. The system path fallback handles distributions that package JDK tools separately from the runtime.
+	///
+	/// # Errors
+	/// This function will error if this installation's `bin/<name>` could not be accessed (due to permissions/etc).
+	pub fn resolve_tool(&self, name: &str) -> Result<Option<PathBuf>> {
+		let tool = self.join("bin").join(executable_name(name));
+		match tool.metadata() {
+			Ok(meta) if meta.is_file() => return Ok(Some(tool)),
+			Err(e) if e.kind() != ErrorKind::NotFound => return Err(Error::IoError(e)),
+			_ => {},
+		}
+
+		log::debug!("{:?} not found in {:?}, searching system path", name, self.path);
+		Ok(search_path(name))
+	}
+
+	/// Builds the path of a tool (such as `javac`) relative to the home, like `bin/javac` (or `bin\javac.exe` on Windows), for manifests and other generated references. The tool is not checked for existence.
+	pub fn relative_tool(&self, name: &str) -> PathBuf {
+		Path::new("bin").join(executable_name(name))
+	}
+
+	/// Runs the first `java` executable on the system path with `-XshowSettings:properties -version`, returning every system property it reports (`java.version`, `java.vendor`, `os.arch`, etc). See `JavaHome::properties_of()` for a specific installation.
+	///
+	/// # Errors
+	/// This function will error if there is an issue finding/running a `java` executable from the path, or with `Error::JavaTimeout` if it does not exit within `JavaHome::DEFAULT_TIMEOUT`.
+	pub fn properties() -> Result<BTreeMap<String, String>> {
+		log::debug!("querying system properties from the `java` command on the system path");
+		let output = run_combined("java", &["-XshowSettings:properties", "-version"])?;
+		Ok(parse_properties(&output))
+	}
+
+	/// Runs this home's `java -XshowSettings:properties -version`, returning every system property it reports.
+	///
+	/// Properties are read from both stdout and stderr, with whitespace trimmed. Multi-valued properties (such as `java.library.path`) are printed over several lines by the JVM, and are joined back together with the platform's path separator (`:` or `;`).
+	///
+	/// # Errors
+	/// This function will error if this home's `bin/java` executable could not be run, or with `Error::JavaTimeout` if it does not exit within `JavaHome::DEFAULT_TIMEOUT`.
+	pub fn properties_of(&self) -> Result<BTreeMap<String, String>> {
+		let java = self.java_executable();
+		log::debug!("querying system properties from {:?}", java);
+
+		let output = run_combined(&java, &["-XshowSettings:properties", "-version"])?;
+		Ok(parse_properties(&output))
+	}
+
+	/// Lists this installation's own module locations that exist, for building a `--module-path` argument: the `jmods` folder of packaged modules (JDKs only), then the `lib/modules` runtime image.
+	///
+	/// Application modules can be pushed onto the returned list, and the whole joined with `std::env::join_paths()`. Pre-modular installations (Java 8 and earlier) have neither location, and return an empty list.
+	///
+	/// # Errors
+	/// This function will error if either location exists but could not be accessed.
+	pub fn default_module_path(&self) -> Result<Vec<PathBuf>> {
+		let mut found = Vec::new();
+		for location in [self.path.join("jmods"), self.path.join("lib").join("modules")] {
+			match location.metadata() {
+				Err(e) if e.kind() == ErrorKind::NotFound => {},
+				Err(e) => return Err(Error::IoError(e)),
+				Ok(_) => found.push(location),
+			}
+		}
+		Ok(found)
+	}
+
+	/// Generates the commands for a shell to set `JAVA_HOME` to this installation, and to put its `bin` directory at the front of `PATH`.
+	///
+	/// The home path is quoted as necessary for the shell. Non-UTF-8 paths are converted lossily. `Shell::Cmd` has no reliable way to escape `%` in paths, so paths containing it may be misinterpreted.
+	pub fn export_script(&self, shell: Shell) -> String {
+		let path = self.path.to_string_lossy();
+		match shell {
+			Shell::Bash => format!(
+				"export JAVA_HOME='{}'\nexport PATH=\"$JAVA_HOME/bin:$PATH\"\n",
+				path.replace('\'', "'\\''"),
+			),
+			Shell::Fish => format!(
+				"set -gx JAVA_HOME '{}'\nset -gx PATH \"$JAVA_HOME/bin\" $PATH\n",
+				path.replace('\\', "\\\\").replace('\'', "\\'"),
+			),
+			Shell::PowerShell => format!(
+				"$env:JAVA_HOME = '{}'\n$env:PATH = (Join-Path $env:JAVA_HOME 'bin') + [IO.Path]::PathSeparator + $env:PATH\n",
+				path.replace('\'', "''"),
+			),
+			Shell::Cmd => format!(
+				"set \"JAVA_HOME={}\"\nset \"PATH=%JAVA_HOME%\\bin;%PATH%\"\n",
+				path,
+			),
+		}
+	}
+
+	/// Clears any cached information about this installation, so that it is looked up again on next use.
+	pub fn refresh(&mut self) {
+		#[cfg(feature = "glob")]
+		{
+			self.native_library = OnceLock::new();
+		}
+	}
+
+	/// Runs this home's `java -version`, returning the printed banner.
+	fn version_banner(&self) -> Result<String> {
+		let java = self.java_executable();
+		log::debug!("querying version banner from {:?}", java);
+
+		// the banner is printed to stderr, though some wrappers redirect it to stdout
+		run_combined(&java, &["-version"])
+	}
+
+	/// Finds every Java installation on the system path, followed by those in the Windows registry (with the `registry` feature, see `JavaHome::installations_from_registry()`) and in well-known system directories (see `JavaHome::system_installations()`).
+	///
+	/// Each `java` executable on the system path (and, on Windows, in the winget links directory) is run to query its home directory, in path order. Executables that fail to run, do not report a home, or do not respond within `JavaHome::DEFAULT_TIMEOUT` are skipped (see `JavaHome::installations_with_timeout()` for a different bound). Installations are deduplicated by their canonicalized path, so symlinks to the same installation are only returned once.
+	///
+	/// The runtime nested within a legacy JDK (its `jre` folder, which reports itself as the home of the JDK's `java`) is not listed separately, and is replaced by the JDK containing it. Use `JavaHome::installations_with_nested()` to list both.
+	///
+	/// # Errors
+	/// This function will error if a system installation directory exists but could not be read.
+	pub fn installations() -> Result<Vec<JavaHome>> {
+		JavaHome::installations_iter().collect()
+	}
+
+	/// Lazily finds every Java installation, like `JavaHome::installations()`.
+	///
+	/// Each `java` executable on the system path is only run, and each system directory is only read, once the iterator reaches it. This allows callers to stop early (such as with `Iterator::find`) without querying every remaining installation.
+	///
+	/// An `Err` is yielded for each system installation directory that exists but could not be read, after which iteration continues.
+	pub fn installations_iter() -> impl Iterator<Item = Result<JavaHome>> {
+		JavaHome::enumerate(false, Some(JavaHome::DEFAULT_TIMEOUT))
+	}
+
+	/// Finds every Java installation like `JavaHome::installations()`, but skips any `java` executable that does not report its home within `per_probe`, so a single unresponsive installation cannot stall the scan. Skipped executables are killed.
+	///
+	/// Installations in the system directories are found without running them, so are unaffected by the timeout.
+	///
+	/// # Errors
+	/// This function will error in any situation that `JavaHome::installations()` will.
+	pub fn installations_with_timeout(per_probe: Duration) -> Result<Vec<JavaHome>> {
+		JavaHome::enumerate(false, Some(per_probe)).collect()
+	}
+
+	/// Finds every Java installation like `JavaHome::installations()`, but also lists the runtime nested within each legacy JDK (its `jre` folder) separately, directly after the JDK.
+	///
+	/// # Errors
+	/// This function will error in any situation that `JavaHome::installations()` will.
+	pub fn installations_with_nested() -> Result<Vec<JavaHome>> {
+		JavaHome::enumerate(true, Some(JavaHome::DEFAULT_TIMEOUT)).collect()
+	}
+
+	/// Lazily finds every Java installation, optionally listing the runtimes nested within legacy JDKs, and limiting how long each executable on the path may run for.
+	fn enumerate(include_nested: bool, timeout: Option<Duration>) -> impl Iterator<Item = Result<JavaHome>> {
+		let java = executable_name("java");
+
+		// multiple PATH entries often resolve to the same executable (/bin and /usr/bin, etc)
+		let mut executables = std::collections::HashSet::new();
+		let on_path = std::env::var_os("PATH")
+			.into_iter()
+			.flat_map(|path| std::env::split_paths(&path).collect::<Vec<_>>())
+			.chain(executable_dirs())
+			.map(move |dir| dir.join(&java))
+			.filter(|exe| exe.is_file())
+			.filter(move |exe| executables.insert(exe.canonicalize().unwrap_or_else(|_| exe.clone())))
+			.filter_map(move |exe| {
+				log::debug!("querying java executable on path at {:?}", exe);
+				match JavaHome::query_home_within(&exe, timeout) {
+					Ok(home) => Some(Ok(home)),
+					Err(e) => {
+						log::debug!("\tskipping: {}", e);
+						None
+					},
+				}
+			});
+
+		// read lazily, like the other sources
+		let in_registry = std::iter::once(()).flat_map(|()| registry_homes());
+
+		let in_system_dirs = system_roots()
+			.into_iter()
+			.flat_map(|root| match scan_system_root(&root) {
+				Ok(homes) => homes.into_iter().map(Ok).collect::<Vec<_>>(),
+				Err(e) => vec![Err(e)],
+			});
+
+		let mut seen = std::collections::HashSet::new();
+		on_path.chain(in_registry).chain(in_system_dirs)
+			.flat_map(move |res| {
+				let home = match res {
+					Ok(home) => home,
+					Err(e) => return vec![Err(e)],
+				};
+				// a legacy JDK's java reports its nested jre as the home
+				let (jdk, nested) = match home.enclosing_jdk() {
+					Some(jdk) => (jdk, home),
+					None => {
+						let nested = JavaHome::assume(home.path.join("jre"));
+						(home, nested)
+					},
+				};
+				if include_nested && nested.java_executable().is_file() {
+					vec![Ok(jdk), Ok(nested)]
+				} else {
+					vec![Ok(jdk)]
+				}
+			})
+			.filter(move |res| match res {
+				Ok(home) => seen.insert(home.canonicalize().map_or_else(|_| home.path.clone(), |canonical| canonical.path)),
+				Err(_) => true,
+			})
+	}
+
+	/// Finds every Java installation like `JavaHome::installations()`, collapsing those that report the same vendor, version, and architecture (from the `java.vendor`, `java.version`, and `os.arch` properties), such as a copy of a JDK alongside the original.
+	///
+	/// Of each set of duplicates, the one with the shortest path is kept, in the position of the first of them. Installations whose properties could not be queried are never collapsed.
+	///
+	/// # Errors
+	/// This function will error in any situation that `JavaHome::installations()` will.
+	pub fn distinct_installations() -> Result<Vec<JavaHome>> {
+		let mut distinct: Vec<JavaHome> = Vec::new();
+		let mut seen: HashMap<[Option<String>; 3], usize> = HashMap::new();
+
+		for home in JavaHome::installations()? {
+			let key = match home.properties_of() {
+				Ok(mut props) => [props.remove("java.vendor"), props.remove("java.version"), props.remove("os.arch")],
+				Err(e) => {
+					log::debug!("could not query {:?} ({}), keeping it", home.path, e);
+					distinct.push(home);
+					continue;
+				},
+			};
+
+			match seen.get(&key) {
+				Some(&i) => {
+					if home.path.as_os_str().len() < distinct[i].path.as_os_str().len() {
+						distinct[i] = home;
+					}
+				},
+				None => {
+					seen.insert(key, distinct.len());
+					distinct.push(home);
+				},
+			}
+		}
+
+		Ok(distinct)
+	}
+
+	/// Returns the home with the highest version (see `JavaHome::version()`) from the provided list, or `None` if it is empty. If several homes share the highest version, the first of them is returned.
+	///
+	/// # Errors
+	/// This function will error if the version of any home could not be determined.
+	pub fn newest(homes: &[JavaHome]) -> Result<Option<&JavaHome>> {
+		let mut newest: Option<(&JavaHome, JavaVersion)> = None;
+		for home in homes {
+			let version = home.version()?;
+			if newest.as_ref().is_none_or(|(_, best)| version > *best) {
+				newest = Some((home, version));
+			}
+		}
+		Ok(newest.map(|(home, _)| home))
+	}
+
+	/// Finds every Java installation and gathers its details: those from `JavaHome::installations()` (the system path, Windows registry, and system directories), followed by those from `JavaHome::installations_from_version_managers()`.
+	///
+	/// Installations are deduplicated by their canonicalized path, and each is queried for its properties once. An installation whose details could not be determined is still returned, with those details as `None`. The result is sorted by version, highest first, with installations of unknown version last (otherwise keeping the order they were found in).
+	///
+	/// # Errors
+	/// This function will error in any situation that `JavaHome::installations()` or `JavaHome::installations_from_version_managers()` will.
+	pub fn survey() -> Result<Vec<JavaInstallation>> {
+		let mut seen = std::collections::HashSet::new();
+		let mut found = Vec::new();
+		for home in JavaHome::installations()?.into_iter().chain(JavaHome::installations_from_version_managers()?) {
+			if !seen.insert(home.canonicalize().map_or_else(|_| home.path.clone(), |canonical| canonical.path)) {
+				continue;
+			}
+
+			let (version, vendor) = match home.properties_of() {
+				Ok(mut props) => (props.remove("java.version").and_then(|version| version.parse().ok()), props.remove("java.vendor")),
+				Err(e) => {
+					log::debug!("unable to query the properties of {:?}: {}", home.path, e);
+					(None, None)
+				},
+			};
+			let is_jdk = home.is_jdk();
+			found.push(JavaInstallation { home, version, vendor, is_jdk });
+		}
+
+		// `None` orders first, so reversing the comparison puts unknown versions last
+		found.sort_by(|a, b| b.version.cmp(&a.version));
+		Ok(found)
+	}
+
+	/// Finds every Java installation like `JavaHome::installations()`, returning the one with the highest version (see `JavaHome::version()`). If several share the highest version, the first found is returned.
+	///
+	/// Installations whose version could not be determined are skipped.
+	///
+	/// # Errors
+	/// This function will error with `Error::NoInstallationsFound` if no installation with a known version was found, or in any situation that `JavaHome::installations()` will.
+	pub fn find_highest_version() -> Result<Self> {
+		let mut highest: Option<(JavaHome, JavaVersion)> = None;
+		for home in JavaHome::installations()? {
+			let version = match home.version() {
+				Ok(version) => version,
+				Err(e) => {
+					log::debug!("skipping {:?}, whose version could not be determined: {}", home.path, e);
+					continue;
+				},
+			};
+			if highest.as_ref().is_none_or(|(_, best)| version > *best) {
+				highest = Some((home, version));
+			}
+		}
+		highest.map(|(home, _)| home).ok_or(Error::NoInstallationsFound)
+	}
+
+	/// Finds every Java installation like `JavaHome::installations()`, keeping only long-term support releases (see `JavaVersion::LTS_RELEASES`).
+	///
+	/// Installations whose version could not be determined are skipped.
+	///
+	/// # Errors
+	/// This function will error in any situation that `JavaHome::installations()` will.
+	pub fn installations_lts_only() -> Result<Vec<JavaHome>> {
+		Ok(JavaHome::installations()?
+			.into_iter()
+			.filter(|home| home.version().is_ok_and(|version| version.is_lts()))
+			.collect())
+	}
+
+	/// Finds every Java installation like `JavaHome::installations()`, keeping only those from the given vendor (such as `"Temurin"` or `"Amazon"`).
+	///
+	/// The vendor matches if it is a case-insensitive substring of the `release` file's `IMPLEMENTOR` or `IMPLEMENTOR_VERSION`, or otherwise of the JVM's `java.vendor` or `java.vendor.version` properties. Installations whose vendor could not be determined are skipped.
+	///
+	/// # Errors
+	/// This function will error in any situation that `JavaHome::installations()` will.
+	pub fn installations_by_vendor(vendor: &str) -> Result<Vec<JavaHome>> {
+		let vendor = vendor.to_lowercase();
+		let matches = |value: Option<&String>| value.is_some_and(|value| value.to_lowercase().contains(&vendor));
+
+		Ok(JavaHome::installations()?
+			.into_iter()
+			.filter(|home| {
+				// the release file is much cheaper to check than running the JVM
+				if let Ok(Some(release)) = home.release() {
+					if matches(release.get("IMPLEMENTOR")) || matches(release.get("IMPLEMENTOR_VERSION")) {
+						return true;
+					}
+				}
+				home.properties_of()
+					.is_ok_and(|props| matches(props.get("java.vendor")) || matches(props.get("java.vendor.version")))
+			})
+			.collect())
+	}
+
+	/// Finds the Java installations at paths matching a glob pattern (such as `/opt/jdks/jdk-*`), for installations kept outside of the well-known system directories.
+	///
+	/// Each matching path is corrected with `JavaHome::normalize()`, and kept if it then contains a `bin/java` executable. Installations are returned in the order their paths matched (sorted by path).
+	///
+	/// # Errors
+	/// This function will error with `Error::GlobPattern` if the pattern is invalid, or with `Error::GlobError` if a matching path could not be read.
+	#[cfg(feature = "glob")]
+	pub fn search(pattern: &str) -> Result<Vec<JavaHome>> {
+		let mut found = Vec::new();
+		for path in glob::glob(pattern)? {
+			let home = JavaHome::assume(path?).normalize();
+			if home.java_executable().is_file() {
+				found.push(home);
+			} else {
+				log::debug!("skipping {:?}, which has no java executable", home.path);
+			}
+		}
+		Ok(found)
+	}
+
+	/// Scans well-known system installation directories for Java installations, such as `/usr/lib/jvm` on Linux, `/Library/Java/JavaVirtualMachines` on MacOS, or `%ProgramFiles%\Java` and `%ProgramFiles%\Microsoft` on Windows.
+	///
+	/// Each subdirectory of these roots that contains a `bin/java` executable (after `JavaHome::normalize()`) is returned, sorted by path within each root. Installations reachable through several paths (such as `/usr/lib/jvm/default-java` symlinks) are only returned once, preferring the non-symlinked path. Roots that do not exist are skipped.
+	///
+	/// # Errors
+	/// This function will error if a root directory exists but could not be read.
+	pub fn system_installations() -> Result<Vec<JavaHome>> {
+		let mut homes = Vec::new();
+		let mut seen = std::collections::HashSet::new();
+
+		for root in system_roots() {
+			for home in scan_system_root(&root)? {
+				let key = home.canonicalize().map_or_else(|_| home.path.clone(), |canonical| canonical.path);
+				if seen.insert(key) {
+					homes.push(home);
+				}
+			}
+		}
+
+		Ok(homes)
+	}
+
+	/// Reads the Java installations registered by Windows installers, from the `JavaHome` value of each version subkey under `HKLM\SOFTWARE\JavaSoft`.
+	///
+	/// Both the pre-Java 9 (`Java Development Kit`, `Java Runtime Environment`) and later (`JDK`, `JRE`) key families are read, from both the 64-bit and 32-bit registry views. Each home is corrected with `JavaHome::normalize()`, and installations registered under several versions (such as both `1.8` and `1.8.0_292`) are only returned once. Missing keys are skipped, so a system without any registered installations returns an empty list.
+	///
+	/// # Errors
+	/// This function will error with `Error::IoError` if a key exists but could not be read.
+	#[cfg(all(target_os = "windows", feature = "registry"))]
+	pub fn installations_from_registry() -> Result<Vec<JavaHome>> {
+		use winreg::enums::{KEY_READ, KEY_WOW64_32KEY, KEY_WOW64_64KEY};
+
+		const FAMILIES: &[&str] = &[
+			r"SOFTWARE\JavaSoft\JDK",
+			r"SOFTWARE\JavaSoft\Java Development Kit",
+			r"SOFTWARE\JavaSoft\JRE",
+			r"SOFTWARE\JavaSoft\Java Runtime Environment",
+		];
+		let missing = |e: &std::io::Error| e.kind() == ErrorKind::NotFound;
+
+		let mut homes = Vec::new();
+		let mut seen = std::collections::HashSet::new();
+		for view in [KEY_WOW64_64KEY, KEY_WOW64_32KEY] {
+			for family in FAMILIES {
+				let key = match winreg::HKLM.open_subkey_with_flags(family, KEY_READ | view) {
+					Err(e) if missing(&e) => continue,
+					Err(e) => return Err(Error::IoError(e)),
+					Ok(key) => key,
+				};
+				log::debug!("reading java installations from registry key {:?}", family);
+
+				for version in key.enum_keys() {
+					let version = version.map_err(Error::IoError)?;
+					let path: OsString = match key.open_subkey_with_flags(&version, KEY_READ | view).and_then(|sub| sub.get_value("JavaHome")) {
+						Err(e) if missing(&e) => continue,
+						Err(e) => return Err(Error::IoError(e)),
+						Ok(path) => path,
+					};
+					let home = JavaHome::assume(path).normalize();
+					if seen.insert(home.canonicalize().map_or_else(|_| home.path.clone(), |canonical| canonical.path)) {
+						homes.push(home);
+					}
+				}
+			}
+		}
+
+		Ok(homes)
+	}
+
+	/// Returns the home of this installation's runtime, which differs between layout generations:
+	/// * JDK 8 and earlier nest a complete runtime in a `jre` folder, which is returned (as detected by a `jre/bin/java` executable)
+	/// * JDK 9 and later (and JREs of any version) are their own runtime, so the home itself is returned (as detected by a `bin/java` executable)
+	///
+	/// # Errors
+	/// This will error with `Error::BadJavaHomePath` if neither layout has a `java` executable, or with `Error::IoError` if one is unreachable (due to permissions/broken links/etc errors).
+	pub fn jre(&self) -> Result<PathBuf> {
+		let nested = self.path.join("jre");
+		for home in [&nested, &self.path] {
+			match home.join("bin").join(executable_name("java")).metadata() {
+				Ok(meta) if meta.is_file() => return Ok(home.clone()),
+				Err(e) if e.kind() != ErrorKind::NotFound => return Err(Error::IoError(e)),
+				_ => {},
+			}
+		}
+		Err(Error::BadJavaHomePath(self.path.clone()))
+	}
+
+	/// Returns the home of the JDK this installation belongs to, or `None` if it is only a runtime. The development tools are detected by a `bin/javac` executable, as with `JavaHome::is_jdk()`.
+	///
+	/// This is normally the home itself. For the `jre` folder nested within a JDK 8 (or earlier) installation, the enclosing JDK's home is returned instead - the JDK 9 and later layout has no nested runtime, so this only applies to legacy JDKs.
+	///
+	/// # Errors
+	/// This will error with `Error::IoError` if `bin/javac` is unreachable (due to permissions/broken links/etc errors).
+	pub fn jdk(&self) -> Result<Option<PathBuf>> {
+		let homes = std::iter::once(self.clone()).chain(self.enclosing_jdk());
+		for home in homes {
+			match home.path.join("bin").join(executable_name("javac")).metadata() {
+				Ok(meta) if meta.is_file() => return Ok(Some(home.path)),
+				Err(e) if e.kind() != ErrorKind::NotFound => return Err(Error::IoError(e)),
+				_ => {},
+			}
+		}
+		Ok(None)
+	}
+
+	/// Checks if this installation is a JDK, by probing for the `javac` compiler (`bin/javac`, with the platform's executable suffix).
+	///
+	/// For the `jre` folder nested within a legacy (JDK 8 and earlier) JDK, the enclosing JDK's `bin/javac` is probed instead, as that is the home these JDK's `java` executables report. Note that a JDK whose `bin` folder was stripped of `javac` is reported as a JRE. Errors accessing the files are treated as the file being absent.
+	pub fn is_jdk(&self) -> bool {
+		let javac = |home: &JavaHome| home.path.join("bin").join(executable_name("javac")).is_file();
+		javac(self) || self.enclosing_jdk().is_some_and(|jdk| javac(&jdk))
+	}
+
+	/// Checks if this installation is only a JRE - it has a `bin/java` executable, but is not a JDK (see `JavaHome::is_jdk()`).
+	///
+	/// A path without a `bin/java` executable is neither a JDK nor a JRE. Errors accessing the files are treated as the file being absent.
+	pub fn is_jre(&self) -> bool {
+		self.java_executable().is_file() && !self.is_jdk()
+	}
+
+	/// Checks if this installation is a runtime without a compiler - like `JavaHome::is_jre()`, but reporting errors instead of treating them as the files being absent.
+	///
+	/// # Errors
+	/// This function will error with `Error::BadJavaHomePath` if there is no `bin/java` executable, or with `Error::IoError` if it or `bin/javac` could not be accessed.
+	pub fn is_jre_only(&self) -> Result<bool> {
+		match self.java_executable().metadata() {
+			Ok(meta) if meta.is_file() => {},
+			Err(e) if e.kind() != ErrorKind::NotFound => return Err(Error::IoError(e)),
+			_ => return Err(Error::BadJavaHomePath(self.path.clone())),
+		}
+
+		match self.path.join("bin").join(executable_name("javac")).metadata() {
+			Err(e) if e.kind() != ErrorKind::NotFound => Err(Error::IoError(e)),
+			_ => Ok(!self.is_jdk()),
+		}
+	}
+
+	/// Returns this installation's `bin` folder, which holds its executables (`java`, `javac`, etc).
+	///
+	/// # Errors
+	/// This will error with `Error::BadJavaHomePath` if the `bin` folder does not exist or is not a directory, or with `Error::IoError` if it is unreachable (due to permissions/broken links/etc errors).
+	pub fn bin(&self) -> Result<PathBuf> {
+		let bin = self.path.join("bin");
+		match bin.metadata() {
+			Err(e) if e.kind() != ErrorKind::NotFound => Err(Error::IoError(e)),
+			Ok(meta) if meta.is_dir() => Ok(bin),
+			_ => Err(Error::BadJavaHomePath(self.path.clone())),
+		}
+	}
+
+	/// Returns this installation's `lib` folder, which holds its libraries and runtime files (`modules`, `ct.sym`, `tools.jar`, etc).
+	///
+	/// # Errors
+	/// This will error with `Error::BadJavaHomePath` if the `lib` folder does not exist or is not a directory, or with `Error::IoError` if it is unreachable (due to permissions/broken links/etc errors).
+	pub fn lib(&self) -> Result<PathBuf> {
+		let lib = self.path.join("lib");
+		match lib.metadata() {
+			Err(e) if e.kind() != ErrorKind::NotFound => Err(Error::IoError(e)),
+			Ok(meta) if meta.is_dir() => Ok(lib),
+			_ => Err(Error::BadJavaHomePath(self.path.clone())),
+		}
+	}
+
+	/// Returns the JDK's `lib/tools.jar`, which holds the compiler and other tool classes of JDK 8 and earlier. Modular JDKs (Java 9 and later) and JREs have none, for which `None` is returned.
+	///
+	/// # Errors
+	/// This will error if the `lib` folder is missing (see `JavaHome::lib()`), or with `Error::IoError` if the file is unreachable (due to permissions/broken links/etc errors).
+	pub fn tools_jar(&self) -> Result<Option<PathBuf>> {
+		lib_file(self.lib()?.join("tools.jar"))
+	}
+
+	/// Returns the `lib/modules` runtime image, which holds the classes of every module in Java 9 and later installations. Pre-modular installations (Java 8 and earlier) have none, for which `None` is returned.
+	///
+	/// # Errors
+	/// This will error if the `lib` folder is missing (see `JavaHome::lib()`), or with `Error::IoError` if the file is unreachable (due to permissions/broken links/etc errors).
+	pub fn modules_file(&self) -> Result<Option<PathBuf>> {
+		lib_file(self.lib()?.join("modules"))
+	}
+
+	/// Locates an executable (such as `javac`, `jar`, or `jshell`) in this installation's `bin` folder, appending the platform's executable suffix (`.exe` on Windows). Returns `None` if it does not exist.
+	///
+	/// Unlike `JavaHome::resolve_tool()`, the system path is not searched.
+	///
+	/// # Errors
+	/// This function will error in any situation that `JavaHome::bin()` will, or if the executable could not be accessed.
+	pub fn executable(&self, name: &str) -> Result<Option<PathBuf>> {
+		let exe = self.bin()?.join(executable_name(name));
+		match exe.metadata() {
+			Ok(meta) if meta.is_file() => Ok(Some(exe)),
+			Err(e) if e.kind() != ErrorKind::NotFound => Err(Error::IoError(e)),
+			_ => Ok(None),
+		}
+	}
+
+	/// Returns this installation's `jmods` folder of packaged modules, as used by `jlink` to build custom runtime images.
+	///
+	/// Only modular JDKs (Java 9 and later) have a `jmods` folder - its absence identifies a JRE or a pre-modular JDK, for which `None` is returned. Note that some distributions omit it from their JDKs to save space, with `jlink` then working from the runtime image instead.
+	///
+	/// # Errors
+	/// This function will error if the `jmods` folder is unreachable (due to permissions/broken links/etc errors).
+	pub fn jmods(&self) -> Result<Option<PathBuf>> {
+		let jmods = self.path.join("jmods");
+		match jmods.metadata() {
+			Err(e) if e.kind() != ErrorKind::NotFound => Err(Error::IoError(e)),
+			Ok(meta) if meta.is_dir() => Ok(Some(jmods)),
+			_ => Ok(None),
+		}
+	}
+
+	/// If the JDK is installed, returns a list of all the include folders necesssary to load JNI/etc headers.
+	#[cfg(feature = "glob")]
+	pub fn include(&self) -> Result<Option<Vec<PathBuf>>> {
+		self.include_dirs()?
+			.map(Iterator::collect)
+			.transpose()
+	}
+
+	/// Like `JavaHome::include()`, but yields the include folders lazily, so callers only needing the first few (or filtering them) do not collect every nested folder. The base `include` folder is yielded first.
+	///
+	/// Homes with non-utf8 paths are walked eagerly rather than globbed, then yielded from the collected list.
+	///
+	/// # Errors
+	/// This will error with `Error::BadJavaHomePath` if `include` is not a directory, or with `Error::IoError` if it is unreachable (due to permissions/broken links/etc errors). Errors reading the nested folders are yielded by the iterator.
+	#[cfg(feature = "glob")]
+	pub fn include_dirs(&self) -> Result<Option<impl Iterator<Item = Result<PathBuf>>>> {
+		let base = self.join("include");
+		log::debug!("looking for $JAVA_HOME/include at {:?}", base);
+
+		match base.metadata() {
+			Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+			Err(e) => return Err(Error::IoError(e)),
+			Ok(meta) if meta.is_dir() => {},
+			Ok(_) => return Err(Error::BadJavaHomePath(self.path.clone())),
+		}
+
+		let nested: Box<dyn Iterator<Item = Result<PathBuf>>> = match base.to_str() {
+			Some(base_str) => {
+				let escaped = glob::Pattern::escape(base_str);
+				let pattern = escaped + "/**/*/";
+				// platform dependent dirs
+				Box::new(glob::glob(&pattern).unwrap().map(|dir| dir.map_err(Error::GlobError)))
+			},
+			None => {
+				log::debug!("\tpath is not utf-8, walking directory instead of globbing");
+				let mut dirs = Vec::new();
+				let walked = walk::walk(&base, |path, meta| {
+					if meta.is_dir() {
+						dirs.push(path.to_path_buf());
+					}
+					std::ops::ControlFlow::Continue(())
+				});
+				match walked {
+					Ok(()) => Box::new(dirs.into_iter().map(Ok)),
+					Err(e) => Box::new(std::iter::once(Err(Error::IoError(e)))),
+				}
+			},
+		};
+
+		// include base dir
+		Ok(Some(std::iter::once(Ok(base)).chain(nested)))
+	}
+
+	/// Returns exactly the include directories needed for the JNI headers, to pass as `-I` flags (such as to `bindgen` or `cc`): the directory holding `jni.h`, then the platform subdirectory holding `jni_md.h` (`include/linux`, `include/win32`, `include/darwin`, etc).
+	///
+	/// If several subdirectories hold a `jni_md.h`, the one named for the built platform is preferred. Returns `None` if the installation has no JNI headers, such as a JRE, matching `JavaHome::include()`.
+	///
+	/// # Errors
+	/// This function will error if the `include` directory or its subdirectories could not be read.
+	pub fn jni_include_dirs(&self) -> Result<Option<Vec<PathBuf>>> {
+		const PLATFORM_DIR: &str = if cfg!(target_os = "windows") { "win32" } else if cfg!(target_os = "macos") { "darwin" } else { std::env::consts::OS };
+
+		let base = self.path.join("include");
+		match base.join("jni.h").metadata() {
+			Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+			Err(e) => return Err(Error::IoError(e)),
+			Ok(_) => {},
+		}
+		if base.join("jni_md.h").is_file() {
+			return Ok(Some(vec![base]));
+		}
+
+		let mut platforms = std::fs::read_dir(&base).map_err(Error::IoError)?
+			.filter_map(|entry| entry.ok())
+			.map(|entry| entry.path())
+			.filter(|dir| dir.join("jni_md.h").is_file())
+			.collect::<Vec<_>>();
+		platforms.sort_by_key(|dir| (dir.file_name() != Some(PLATFORM_DIR.as_ref()), dir.clone()));
+
+		let mut dirs = vec![base];
+		dirs.extend(platforms.into_iter().next());
+		Ok(Some(dirs))
+	}
+
+	/// Like `JavaHome::include()`, but only returns directories that directly contain at least one `.h` header file.
+	///
+	/// This omits directories holding only other content, keeping compiler include path lists minimal.
+	#[cfg(feature = "glob")]
+	pub fn include_with_headers_only(&self) -> Result<Option<Vec<PathBuf>>> {
+		let dirs = match self.include()? {
+			Some(dirs) => dirs,
+			None => return Ok(None),
+		};
+
+		let mut with_headers = Vec::with_capacity(dirs.len());
+		for dir in dirs {
+			let has_header = std::fs::read_dir(&dir).map_err(Error::IoError)?
+				.filter_map(|entry| entry.ok())
+				.any(|entry| {
+					entry.path().extension() == Some("h".as_ref())
+						&& entry.file_type().is_ok_and(|ty| !ty.is_dir())
+				});
+			if has_header {
+				with_headers.push(dir);
+			}
+		}
+
+		Ok(Some(with_headers))
+	}
+
+	/// The path to the JVM's platform-specific native library, suitable for linking with. (`jvm.dll`/`libjvm.so`/`libjli.dylib`)
+	///
+	/// If the installation has native libraries for several VM variants, the server VM's is preferred (see `JavaHome::native_library_variant()`), otherwise the first one found is returned.
+	///
+	/// The located path is cached within this `JavaHome`, so later calls do not search the installation again.
+	#[cfg(feature = "glob")]
+	pub fn native_library(&self) -> Result<PathBuf> {
+		if let Some(path) = self.native_library.get() {
+			return Ok(path.clone());
+		}
+
+		// developer note: if on linux, LD_LIBRARY_PATH may need to be set for the system loader to find it
+		// alternatively, is there a way to tell cargo to use the absolute path?
+		let path = self.native_library_for(BUILT_TARGET_OS)?;
+
+		// another thread may have beat us to it, either way the value is equivalent
+		let _ = self.native_library.set(path.clone());
+		Ok(path)
+	}
+
+	/// The folder containing the JVM's native library (as located by `JavaHome::native_library()`), such as `lib/server`. This is the folder to add to the linker's search path, or to the system loader's (`LD_LIBRARY_PATH`, `DYLD_LIBRARY_PATH`, or `PATH` on Windows) at runtime.
+	///
+	/// # Errors
+	/// This function will error in any situation that `JavaHome::native_library()` will.
+	#[cfg(feature = "glob")]
+	pub fn native_library_dir(&self) -> Result<PathBuf> {
+		let library = self.native_library()?;
+		Ok(library.parent().map(Path::to_path_buf).unwrap_or(library))
+	}
+
+	/// Locates every copy of the JVM's platform-specific native library within the installation, such as both `bin/client/jvm.dll` and `bin/server/jvm.dll`. Unlike `JavaHome::native_library()`, no copy is preferred and nothing is cached.
+	///
+	/// An installation without a native library returns an empty list, rather than `Error::NoNativeLibrary`.
+	#[cfg(feature = "glob")]
+	pub fn native_libraries(&self) -> Result<Vec<PathBuf>> {
+		find_all_files_under(&self.path, NATIVE_LIBRARY_FILENAME)
+	}
+
+	/// Locates every copy of the JVM's native library like `JavaHome::native_libraries()`, each paired with the name of the folder containing it - the VM variant for HotSpot installations (`server`, `client`, `minimal`, etc). This allows offering a choice among the variants an installation ships.
+	///
+	/// Where the library is not kept in a variant folder (such as MacOS's `libjli.dylib` in `lib`), the folder name is still used, as a best-effort label. Non-UTF-8 folder names are converted lossily.
+	#[cfg(feature = "glob")]
+	pub fn native_library_variants(&self) -> Result<Vec<(String, PathBuf)>> {
+		Ok(self.native_libraries()?
+			.into_iter()
+			.map(|library| {
+				let label = library.parent()
+					.and_then(Path::file_name)
+					.map(|name| name.to_string_lossy().into_owned())
+					.unwrap_or_default();
+				(label, library)
+			})
+			.collect())
+	}
+
+	/// Locates the native library of a specific VM variant, found within the variant's folder (such as `bin/server/jvm.dll` or `lib/client/libjvm.so`). The result is not cached.
+	///
+	/// # Errors
+	/// This function will error with `Error::NoNativeLibrary` if the installation has no native library for the variant.
+	#[cfg(feature = "glob")]
+	pub fn native_library_variant(&self, variant: VmVariant) -> Result<PathBuf> {
+		find_all_files_under(&self.path, NATIVE_LIBRARY_FILENAME)?
+			.into_iter()
+			.find(|path| is_variant(path, variant))
+			.ok_or(Error::NoNativeLibrary)
+	}
+
+	/// Like `JavaHome::native_library()`, but searches for the native library of the provided operating system instead of the one this crate was built for, such as for a build script pointed at a JDK for its compilation target. The server VM's library is preferred in the same way. The result is not cached.
+	///
+	/// # Errors
+	/// This function will error with `Error::NoNativeLibrary` if the library is not found within the home.
+	#[cfg(feature = "glob")]
+	pub fn native_library_for(&self, target_os: TargetOs) -> Result<PathBuf> {
+		self.find_native_library_named(target_os.native_library_filename())
+	}
+
+	/// Like `JavaHome::native_library()`, but searches for a library with the provided filename instead, such as for custom JVM builds or companion libraries (`libjsig.so`, etc). The server VM's copy is preferred in the same way. The result is not cached.
+	///
+	/// # Errors
+	/// This function will error with `Error::NoNativeLibrary` if no file with the name is found within the home.
+	#[cfg(feature = "glob")]
+	pub fn find_native_library_named(&self, filename: &str) -> Result<PathBuf> {
+		log::debug!("looking for JVM native library {:?}", filename);
+		let mut found = find_all_files_under(&self.path, filename)?;
+		let server = found.iter().position(|path| is_variant(path, VmVariant::Server));
+		match server {
+			Some(i) => Ok(found.swap_remove(i)),
+			None if !found.is_empty() => Ok(found.swap_remove(0)),
+			None => Err(Error::NoNativeLibrary),
+		}
+	}
+
+	/// Lists the libraries within this installation that the native library (see `JavaHome::native_library()`) depends on, such as `libjli`, for bundling alongside it.
+	///
+	/// The dependency lists of ELF (`DT_NEEDED`), PE (imported DLLs), and Mach-O (linked dylibs) libraries are read, and each dependency is looked up by filename within the home (case-insensitively on Windows). Dependencies of those libraries are followed in turn. Dependencies outside of the home, such as the system's C library, are not listed. Libraries that the JVM loads at runtime instead of linking to (such as `libjava` and `libnet`) are not part of these lists, and must be found separately.
+	///
+	/// # Errors
+	/// This function will error in any situation that `JavaHome::native_library()` will, or if the home or a library could not be read.
+	#[cfg(feature = "glob")]
+	pub fn runtime_dependencies(&self) -> Result<Vec<PathBuf>> {
+		let key = |name: &str| if cfg!(windows) { name.to_lowercase() } else { name.to_string() };
+
+		// index every file by name once, rather than searching the home for each dependency
+		let mut files: HashMap<String, PathBuf> = HashMap::new();
+		walk::walk(&self.path, |path, meta| {
+			if meta.is_file() {
+				if let Some(name) = path.file_name().and_then(|name| name.to_str()) {
+					files.entry(key(name)).or_insert_with(|| path.to_path_buf());
+				}
+			}
+			std::ops::ControlFlow::Continue(())
+		}).map_err(Error::IoError)?;
+
+		let library = self.native_library()?;
+		let mut seen = std::collections::HashSet::new();
+		seen.insert(library.clone());
+		let mut pending = vec![library];
+		let mut dependencies = Vec::new();
+
+		while let Some(library) = pending.pop() {
+			let data = std::fs::read(&library).map_err(Error::IoError)?;
+			let needed = match needed_libraries(&data) {
+				Some(needed) => needed,
+				None => {
+					log::debug!("{:?} is not a recognized library format, skipping its dependencies", library);
+					continue;
+				},
+			};
+			log::debug!("{:?} depends on {:?}", library, needed);
+
+			for name in needed {
+				// install names may be paths (`@rpath/libjli.dylib`)
+				let name = name.rsplit(['/', '\\']).next().unwrap_or(&name);
+				if let Some(path) = files.get(&key(name)) {
+					if seen.insert(path.clone()) {
+						dependencies.push(path.clone());
+						pending.push(path.clone());
+					}
+				}
+			}
+		}
+
+		Ok(dependencies)
+	}
+
+	/// Locates the JVM's native library (via `JavaHome::native_library()`) and loads it into the current process.
+	///
+	/// On Windows, the library's own directory is searched for its dependencies (rather than the current directory, `PATH`, etc). This does not include the installation's `bin` directory, so if a runtime DLL such as `vcruntime140.dll` is only bundled there, it must already be loaded or findable through the default search paths. On other platforms, the libraries within an installation locate their dependencies relative to themselves, so `LD_LIBRARY_PATH` does not need to be set when loading by absolute path.
+	///
+	/// # Errors
+	/// This function will error in any situation that `JavaHome::native_library()` will, or with `Error::NativeLibraryLoadFailed` if the library could not be loaded - such as when it was built for a different architecture than the current process.
+	///
+	/// # Safety
+	/// Loading the library runs its initialization routines, which may do anything. The caller must trust the installation the library is loaded from. See `libloading::Library::new` for details.
+	#[cfg(all(feature = "glob", feature = "libloading"))]
+	pub unsafe fn load_native_library(&self) -> Result<libloading::Library> {
+		let path = self.native_library()?;
+		log::debug!("loading JVM native library at {:?}", path);
+
+		#[cfg(target_os = "windows")]
+		let library = {
+			use libloading::os::windows::{Library, LOAD_LIBRARY_SEARCH_DEFAULT_DIRS, LOAD_LIBRARY_SEARCH_DLL_LOAD_DIR};
+			// SAFETY: upheld by the caller
+			unsafe { Library::load_with_flags(&path, LOAD_LIBRARY_SEARCH_DLL_LOAD_DIR | LOAD_LIBRARY_SEARCH_DEFAULT_DIRS) }.map(libloading::Library::from)
+		};
+		#[cfg(not(target_os = "windows"))]
+		// SAFETY: upheld by the caller
+		let library = unsafe { libloading::Library::new(&path) };
+
+		library.map_err(Error::NativeLibraryLoadFailed)
+	}
+
+	/// Locates the JVM's native library like `JavaHome::native_library()`, then confirms it can be loaded by loading it with `JavaHome::load_native_library()` and immediately unloading it. This catches installations whose library is present but unusable (a missing dependency, a different architecture than the current process, etc) before relying on it.
+	///
+	/// # Errors
+	/// This function will error in any situation that `JavaHome::load_native_library()` will.
+	///
+	/// # Safety
+	/// The same as for `JavaHome::load_native_library()`, as the library's initialization (and finalization) routines are run.
+	#[cfg(all(feature = "glob", feature = "libloading"))]
+	pub unsafe fn native_library_checked(&self) -> Result<PathBuf> {
+		// SAFETY: upheld by the caller
+		drop(unsafe { self.load_native_library() }?);
+		self.native_library()
+	}
+
+	/// The path to the JVM's static library (`libjvm.a`, or `jvm.lib` on Windows), if the installation ships one.
+	///
+	/// Most distributions do not include a static library, so this is mainly useful to check whether static linking is possible at all. Note that on Windows, distributions commonly ship `jvm.lib` as an import library for `jvm.dll` rather than a true static library.
+	///
+	/// # Errors
+	/// This function will error in any situation that `JavaHome::find_file()` will.
+	#[cfg(feature = "glob")]
+	pub fn static_native_library(&self) -> Result<Option<PathBuf>> {
+		#[cfg(target_os = "windows")]
+		const STATIC_LIBRARY_FILENAME: &str = "jvm.lib";
+		#[cfg(not(target_os = "windows"))]
+		const STATIC_LIBRARY_FILENAME: &str = "libjvm.a";
+
+		log::debug!("looking for JVM static library {:?}", STATIC_LIBRARY_FILENAME);
+		self.find_file(STATIC_LIBRARY_FILENAME)
+	}
+
+	/// Computes the SHA-256 digest of the JVM's native library (as located by `JavaHome::native_library()`), for tracking changes to the library an application links against.
+	///
+	/// # Errors
+	/// This function will error in any situation that `JavaHome::native_library()` will, or if the located library could not be read.
+	#[cfg(all(feature = "glob", feature = "sha2"))]
+	pub fn native_library_sha256(&self) -> Result<[u8; 32]> {
+		use sha2::Digest;
+
+		let path = self.native_library()?;
+		log::debug!("hashing JVM native library at {:?}", path);
+
+		let mut file = std::fs::File::open(&path).map_err(Error::IoError)?;
+		let mut hasher = sha2::Sha256::new();
+		std::io::copy(&mut file, &mut hasher).map_err(Error::IoError)?;
+		Ok(hasher.finalize().into())
+	}
+
+	/// Walks the home directory, returning every entry for which the predicate (given the entry's path and metadata) returns true.
+	///
+	/// Entries are returned in the same order `JavaHome::find_file()` searches them. Symlinks are followed, so the metadata describes their targets.
+	///
+	/// # Errors
+	/// This function will error if the home directory, or any directory within it, could not be read.
+	pub fn find_by<F: Fn(&Path, &std::fs::Metadata) -> bool>(&self, pred: F) -> Result<Vec<PathBuf>> {
+		let mut found = Vec::new();
+		walk::walk(&self.path, |path, meta| {
+			if pred(path, meta) {
+				found.push(path.to_path_buf());
+			}
+			std::ops::ControlFlow::Continue(())
+		}).map_err(Error::IoError)?;
+		Ok(found)
+	}
+
+	/// A convience function to search for a specific file within the home directory. Matches the filename literally.
+	///
+	/// Homes with non-utf8 paths are searched by walking the directory tree rather than globbing, with equivalent results.
+	#[cfg(feature = "glob")]
+	pub fn find_file(&self, file: &str) -> Result<Option<PathBuf>> {
+		find_file_under(&self.path, file)
+	}
+
+	/// Like `JavaHome::find_file()`, but returns every file with the name within the home directory, in the order they were found. Returns an empty list if there are none.
+	#[cfg(feature = "glob")]
+	pub fn find_all_files(&self, file: &str) -> Result<Vec<PathBuf>> {
+		find_all_files_under(&self.path, file)
+	}
+
+	/// Like `JavaHome::find_file()`, but only searches within the listed subdirectories of the home (such as `["lib"]` for the native library), in order. This avoids searching large folders (`include`, `jmods`, `legal`, etc) that can't contain the file.
+	///
+	/// Subdirectories that do not exist are skipped.
+	#[cfg(feature = "glob")]
+	pub fn find_file_in(&self, subdirs: &[&str], file: &str) -> Result<Option<PathBuf>> {
+		for subdir in subdirs {
+			let base = self.path.join(subdir);
+			if !base.is_dir() {
+				continue;
+			}
+			if let Some(found) = find_file_under(&base, file)? {
+				return Ok(Some(found));
+			}
+		}
+		Ok(None)
+	}
+
+	/// A convience function to search for a specific folder within the home directory. Matches the folder name literally.
+	///
+	/// Homes with non-utf8 paths are searched by walking the directory tree rather than globbing, with equivalent results.
+	#[cfg(feature = "glob")]
+	pub fn find_folder(&self, folder: &str) -> Result<Option<PathBuf>> {
+		let base = &self.path;
+		let base_str = match base.to_str() {
+			Some(base_str) => base_str,
+			None => return walk::find(base, |path, meta| meta.is_dir() && path.file_name() == Some(folder.as_ref())).map_err(Error::IoError),
+		};
+		let base_escaped = glob::Pattern::escape(base_str);
+		let fold_escaped = glob::Pattern::escape(folder);
+		let pattern = base_escaped + "/**/" + &fold_escaped + "/";
+		glob::glob(&pattern)
+			.unwrap() // pattern should always be valid
+			.next()
+			.transpose()
+			.map_err(Error::GlobError)
+	}
+
+	/// Like `JavaHome::find_file()`, but matches the file name case-insensitively (such as `JNI.h` for `jni.h`). Only ASCII letters are compared case-insensitively, other characters must match exactly.
+	///
+	/// The home directory is walked rather than globbed, so this does not require the `glob` feature, and supports non-utf8 paths.
+	///
+	/// # Errors
+	/// This function will error if the home directory, or any directory within it, could not be read.
+	pub fn find_file_ci(&self, file: &str) -> Result<Option<PathBuf>> {
+		walk::find(&self.path, |path, _| path.file_name().is_some_and(|name| name.eq_ignore_ascii_case(file))).map_err(Error::IoError)
+	}
+
+	/// Like `JavaHome::find_folder()`, but matches the folder name case-insensitively, as described by `JavaHome::find_file_ci()`.
+	///
+	/// # Errors
+	/// This function will error if the home directory, or any directory within it, could not be read.
+	pub fn find_folder_ci(&self, folder: &str) -> Result<Option<PathBuf>> {
+		walk::find(&self.path, |path, meta| meta.is_dir() && path.file_name().is_some_and(|name| name.eq_ignore_ascii_case(folder))).map_err(Error::IoError)
+	}
+
+	/// Like `JavaHome::find_file()`, but matches the file with a glob pattern (such as `libjvm.*`) rather than literally.
+	///
+	/// The pattern is not anchored at the home directory: it is searched for as `<home>/**/<pattern>`, so it may match starting from any folder within the home. A pattern with several components (such as `lib/*/libjvm.so`) matches those trailing components of a path at any depth, and a `**` component within it matches any number of further folders. Patterns are matched per component, so `*` never matches a `/`.
+	///
+	/// Homes with non-utf8 paths are searched by walking the directory tree rather than globbing, with equivalent results.
+	///
+	/// # Errors
+	/// This function will error with `Error::GlobPattern` if the pattern is invalid, or with `Error::GlobError` if a path could not be read.
+	#[cfg(feature = "glob")]
+	pub fn find_file_glob(&self, pattern: &str) -> Result<Option<PathBuf>> {
+		glob_under(&self.path, pattern, false)
+	}
+
+	/// Like `JavaHome::find_folder()`, but returns every folder with the name within the home directory, in the order they were found. Returns an empty list if there are none.
+	#[cfg(feature = "glob")]
+	pub fn find_all_folders(&self, folder: &str) -> Result<Vec<PathBuf>> {
+		let base = &self.path;
+		let base_str = match base.to_str() {
+			Some(base_str) => base_str,
+			None => return self.find_by(|path, meta| meta.is_dir() && path.file_name() == Some(folder.as_ref())),
+		};
+		let base_escaped = glob::Pattern::escape(base_str);
+		let fold_escaped = glob::Pattern::escape(folder);
+		let pattern = base_escaped + "/**/" + &fold_escaped + "/";
+		glob::glob(&pattern)
+			.unwrap() // pattern should always be valid
+			.collect::<std::result::Result<Vec<PathBuf>, glob::GlobError>>()
+			.map_err(Error::GlobError)
+	}
+
+	/// Like `JavaHome::find_folder()`, but matches the folder with a glob pattern (such as `jdk-17*`) rather than literally. The pattern is searched for as with `JavaHome::find_file_glob()`.
+	///
+	/// # Errors
+	/// This function will error with `Error::GlobPattern` if the pattern is invalid, or with `Error::GlobError` if a path could not be read.
+	#[cfg(feature = "glob")]
+	pub fn find_folder_glob(&self, pattern: &str) -> Result<Option<PathBuf>> {
+		glob_under(&self.path, pattern, true)
+	}
+}
+
+/// Searches for the first path under `base` matching the glob pattern at any depth, as described by `JavaHome::find_file_glob()`. Only folders are matched if `folders` is set.
+#[cfg(feature = "glob")]
+fn glob_under(base: &Path, pattern: &str, folders: bool) -> Result<Option<PathBuf>> {
+	let base_str = match base.to_str() {
+		Some(base_str) => base_str,
+		None => {
+			let pattern = glob::Pattern::new(&format!("**/{}", pattern))?;
+			return walk::find(base, |path, meta| {
+				(!folders || meta.is_dir()) && path.strip_prefix(base).is_ok_and(|relative| pattern.matches_path(relative))
+			}).map_err(Error::IoError);
+		},
+	};
+	let mut full = glob::Pattern::escape(base_str) + "/**/" + pattern;
+	if folders {
+		full.push('/');
+	}
+	glob::glob(&full)?
+		.next()
+		.transpose()
+		.map_err(Error::GlobError)
+}
+
+/// Searches for the first file with the provided name under `base`, as described by `JavaHome::find_file()`.
+#[cfg(feature = "glob")]
+fn find_file_under(base: &Path, file: &str) -> Result<Option<PathBuf>> {
+	let base_str = match base.to_str() {
+		Some(base_str) => base_str,
+		None => return walk::find(base, |path, _| path.file_name() == Some(file.as_ref())).map_err(Error::IoError),
+	};
+	let base_escaped = glob::Pattern::escape(base_str);
+	let file_escaped = glob::Pattern::escape(file);
+	let pattern = base_escaped + "/**/" + &file_escaped;
+	glob::glob(&pattern)
+		.unwrap() // pattern should always be valid
+		.next()
+		.transpose()
+		.map_err(Error::GlobError)
+}
+
+/// Checks if a native library belongs to a VM variant, by the name of the folder it is in.
+#[cfg(feature = "glob")]
+fn is_variant(library: &Path, variant: VmVariant) -> bool {
+	library.parent().and_then(Path::file_name) == Some(variant.folder_name().as_ref())
+}
+
+/// Searches for every file with the provided name under `base`, in the order `JavaHome::find_file()` would find them.
+#[cfg(feature = "glob")]
+fn find_all_files_under(base: &Path, file: &str) -> Result<Vec<PathBuf>> {
+	let base_str = match base.to_str() {
+		Some(base_str) => base_str,
+		None => {
+			let mut found = Vec::new();
+			walk::walk(base, |path, _| {
+				if path.file_name() == Some(file.as_ref()) {
+					found.push(path.to_path_buf());
+				}
+				std::ops::ControlFlow::Continue(())
+			}).map_err(Error::IoError)?;
+			return Ok(found);
+		},
+	};
+	let base_escaped = glob::Pattern::escape(base_str);
+	let file_escaped = glob::Pattern::escape(file);
+	let pattern = base_escaped + "/**/" + &file_escaped;
+	glob::glob(&pattern)
+		.unwrap() // pattern should always be valid
+		.collect::<std::result::Result<Vec<PathBuf>, glob::GlobError>>()
+		.map_err(Error::GlobError)
+}
+
+/// Reads a property (such as `Status`) of a file's Authenticode signature with PowerShell's `Get-AuthenticodeSignature`, returning it trimmed.
+#[cfg(target_os = "windows")]
+fn authenticode_query(file: &Path, property: &str) -> Result<String> {
+	// the path is passed through the environment to avoid quoting it into the script
+	let output = Command::new("powershell")
+		.args(["-NoProfile", "-NonInteractive", "-Command"])
+		.arg(format!("(Get-AuthenticodeSignature -LiteralPath $env:JVM_FIND_SIGNED_FILE).{}", property))
+		.env("JVM_FIND_SIGNED_FILE", file)
+		.output()
+		.map_err(Error::SignatureToolFailed)?;
+	Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Reads the names of the libraries that an ELF, PE, or Mach-O file depends on, or `None` if it is none of these.
+#[cfg(feature = "glob")]
+fn needed_libraries(data: &[u8]) -> Option<Vec<String>> {
+	elf::needed_libraries(data)
+		.or_else(|| pe::needed_libraries(data))
+		.or_else(|| macho::needed_libraries(data))
+}
+
+/// Searches the system path for the first executable with the provided name (the platform's executable suffix is appended).
+fn search_path(name: &str) -> Option<PathBuf> {
+	search_path_in(&std::env::var_os("PATH")?, name)
+}
+
+/// Searches a `PATH`-style list of directories for the first executable with the provided name (the platform's executable suffix is appended).
+fn search_path_in(path: &OsStr, name: &str) -> Option<PathBuf> {
+	let file = executable_name(name);
+	std::env::split_paths(path)
+		.map(|dir| dir.join(&file))
+		.find(|path| path.metadata().is_ok_and(|meta| meta.is_file() && is_executable(path, &meta)))
+}
+
+/// Checks if a file is executable - having an executable permission bit on Unix, or an `.exe` extension on Windows.
+fn is_executable(path: &Path, meta: &std::fs::Metadata) -> bool {
+	#[cfg(unix)]
+	{
+		use std::os::unix::fs::PermissionsExt;
+		let _ = path;
+		meta.permissions().mode() & 0o111 != 0
+	}
+
+	#[cfg(not(unix))]
+	{
+		let _ = meta;
+		path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("exe"))
+	}
+}
+
+/// Returns the file if it exists, for the optional files of `JavaHome::lib()`.
+fn lib_file(file: PathBuf) -> Result<Option<PathBuf>> {
+	match file.metadata() {
+		Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+		Err(e) => Err(Error::IoError(e)),
+		Ok(meta) if meta.is_file() => Ok(Some(file)),
+		Ok(_) => Ok(None),
+	}
+}
+
+/// The current user's home directory - `$HOME`, or `%USERPROFILE%` on Windows.
+fn user_home() -> Option<PathBuf> {
+	let var = if cfg!(windows) { "USERPROFILE" } else { "HOME" };
+	std::env::var_os(var).filter(|home| !home.is_empty()).map(PathBuf::from)
+}
+
+/// The directory asdf keeps its installations in - `$ASDF_DATA_DIR`, or `~/.asdf`.
+fn asdf_data_dir() -> Option<PathBuf> {
+	std::env::var_os("ASDF_DATA_DIR")
+		.filter(|dir| !dir.is_empty())
+		.map(PathBuf::from)
+		.or_else(|| std::env::var_os("HOME").filter(|home| !home.is_empty()).map(|home| PathBuf::from(home).join(".asdf")))
+}
+
+/// The directories that system-wide Java installations are conventionally placed in, for this platform.
+fn system_roots() -> Vec<PathBuf> {
+	let mut roots = Vec::new();
+
+	#[cfg(target_os = "windows")]
+	for var in &["ProgramFiles", "ProgramFiles(x86)"] {
+		if let Some(dir) = std::env::var_os(var).filter(|dir| !dir.is_empty()) {
+			let dir = PathBuf::from(dir);
+			roots.push(dir.join("Java"));
+			roots.push(dir.join("Eclipse Adoptium"));
+			// the Microsoft Build of OpenJDK (MSI or winget) installs as Microsoft\jdk-*, alongside other Microsoft software without a bin\java.exe
+			roots.push(dir.join("Microsoft"));
+		}
+	}
+
+	#[cfg(target_os = "macos")]
+	{
+		roots.push(PathBuf::from("/Library/Java/JavaVirtualMachines"));
+		if let Some(home) = std::env::var_os("HOME").filter(|dir| !dir.is_empty()) {
+			roots.push(PathBuf::from(home).join("Library/Java/JavaVirtualMachines"));
+		}
+	}
+
+	#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+	roots.extend(["/usr/lib/jvm", "/usr/lib64/jvm", "/usr/java", "/opt/java"].iter().map(PathBuf::from));
+
+	roots
+}
+
+/// The installations registered in the Windows registry (see `JavaHome::installations_from_registry()`), or none where registry support is unavailable.
+fn registry_homes() -> Vec<Result<JavaHome>> {
+	#[cfg(all(target_os = "windows", feature = "registry"))]
+	return match JavaHome::installations_from_registry() {
+		Ok(homes) => homes.into_iter().map(Ok).collect(),
+		Err(e) => vec![Err(e)],
+	};
+
+	#[cfg(not(all(target_os = "windows", feature = "registry")))]
+	Vec::new()
+}
+
+/// Lists directories outside of `PATH` that hold `java` executables (or links to them) for installations that aren't in a system installation directory.
+///
+/// On Windows this is the winget links directory, where portable winget packages add their executables.
+fn executable_dirs() -> Vec<PathBuf> {
+	#[cfg(target_os = "windows")]
+	return std::env::var_os("LOCALAPPDATA")
+		.filter(|dir| !dir.is_empty())
+		.map(|local| PathBuf::from(local).join(r"Microsoft\WinGet\Links"))
+		.into_iter()
+		.collect();
+
+	#[cfg(not(target_os = "windows"))]
+	Vec::new()
+}
+
+/// Finds the Java installations directly within a system installation directory, sorted by path with real directories ahead of symlinks to them.
+///
+/// A root that does not exist has no installations.
+fn scan_system_root(root: &Path) -> Result<Vec<JavaHome>> {
+	log::debug!("scanning {:?} for java installations", root);
+	let entries = match std::fs::read_dir(root) {
+		Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+		Err(e) => return Err(Error::IoError(e)),
+		Ok(entries) => entries,
+	};
+
+	let mut found = entries
+		.filter_map(|entry| entry.ok())
+		.map(|entry| JavaHome::assume(entry.path()).normalize())
+		.filter(|home| home.java_executable().is_file())
+		.collect::<Vec<_>>();
+
+	// prefer real directories over symlinks to them, for when duplicates are collapsed
+	found.sort_by_cached_key(|home| (home.path.read_link().is_ok(), home.path.clone()));
+	Ok(found)
+}
+
+/// Parses the flag table printed by `-XX:+PrintFlagsFinal` into a map of flag names to values.
+///
+/// Each flag line has the form `<type> <name> <= or :=> [value] {<origin>}...`, with any other lines (headers, version banners) being ignored.
+fn parse_flags_final(output: &str) -> HashMap<String, String> {
+	output.lines()
+		.filter_map(|line| {
+			let mut tokens = line.split_whitespace();
+			let _ty = tokens.next()?;
+			let name = tokens.next()?;
+			match tokens.next()? {
+				"=" | ":=" => {},
+				_ => return None,
+			}
+			let value = tokens
+				.take_while(|tok| !tok.starts_with('{'))
+				.collect::<Vec<_>>()
+				.join(" ");
+			Some((name.to_string(), value))
+		})
+		.collect()
+}
+
+/// The homes remembered by `JavaHome::find_home_cached()` and `JavaHome::find_active_home_cached()`.
+static CACHED_HOME: Mutex<Option<JavaHome>> = Mutex::new(None);
+static CACHED_ACTIVE_HOME: Mutex<Option<JavaHome>> = Mutex::new(None);
+
+/// Returns the cached home, or runs the finder and caches its home if it succeeds.
+///
+/// The lock is held while the finder runs, so concurrent callers wait for one search rather than each running their own.
+fn cached(cache: &Mutex<Option<JavaHome>>, find: fn() -> Result<JavaHome>) -> Result<JavaHome> {
+	let mut cache = cache.lock().unwrap_or_else(PoisonError::into_inner);
+	if let Some(home) = &*cache {
+		return Ok(home.clone());
+	}
+
+	let home = find()?;
+	*cache = Some(home.clone());
+	Ok(home)
+}
+
+/// Maps an error from starting a `java` executable, distinguishing a missing executable (`Error::JavaNotFound`) from other failures to run it.
+fn spawn_error(e: std::io::Error) -> Error {
+	match e.kind() {
+		ErrorKind::NotFound => Error::JavaNotFound,
+		_ => Error::JavaExecution(e),
+	}
+}
+
+/// Runs a `java` executable with the provided arguments, returning everything it printed.
+///
+/// Both stdout and stderr are redirected into a single pipe (like `2>&1`), so the output keeps the order it was written in regardless of which stream each line went to. The output is decoded with `decode_output()`.
+///
+/// The executable is killed, returning `Error::JavaTimeout`, if it does not exit within `JavaHome::DEFAULT_TIMEOUT`.
+fn run_combined(java: impl AsRef<OsStr>, args: &[&str]) -> Result<String> {
+	run_combined_within(java, args, Some(JavaHome::DEFAULT_TIMEOUT))
+}
+
+/// Runs a `java` executable like `run_combined()`, with the provided timeout instead (or none at all).
+fn run_combined_within(java: impl AsRef<OsStr>, args: &[&str], timeout: Option<Duration>) -> Result<String> {
+	let mut command = Command::new(java);
+	command.args(args);
+	run_command_within(command, timeout)
+}
+
+/// Runs a prepared `java` command (with its arguments, environment, etc already set) like `run_combined_within()`. Its standard streams are overridden.
+fn run_command_within(mut command: Command, timeout: Option<Duration>) -> Result<String> {
+	use std::io::Read;
+
+	let (mut reader, writer) = std::io::pipe().map_err(Error::JavaExecution)?;
+	command.stdin(std::process::Stdio::null())
+		.stdout(writer.try_clone().map_err(Error::JavaExecution)?)
+		.stderr(writer);
+	let spawned = command.spawn();
+	// the `Command` holds our copies of the write end, drop it so this reads until the child exits
+	drop(command);
+	let mut child = spawned.map_err(spawn_error)?;
+
+	let timeout = match timeout {
+		Some(timeout) => timeout,
+		None => {
+			let mut output = Vec::new();
+			reader.read_to_end(&mut output).map_err(Error::IoError)?;
+			child.wait().map_err(Error::JavaExecution)?;
+			return Ok(decode_output(output));
+		},
+	};
+
+	// read on another thread, so a JVM that stops writing without exiting can't block us
+	let (sender, read) = std::sync::mpsc::channel();
+	std::thread::spawn(move || {
+		let mut output = Vec::new();
+		// the receiver is gone if we've already timed out
+		let _ = sender.send(reader.read_to_end(&mut output).map(|_| output));
+	});
+
+	let deadline = std::time::Instant::now() + timeout;
+	while child.try_wait().map_err(Error::JavaExecution)?.is_none() {
+		if std::time::Instant::now() >= deadline {
+			log::debug!("\tjava did not exit within {:?}, killing it", timeout);
+			// it may have exited in the meantime, in which case there's nothing to kill
+			let _ = child.kill();
+			let _ = child.wait();
+			// the reader thread is left to finish on its own, in case a child of java still holds the pipe open
+			return Err(Error::JavaTimeout(timeout));
+		}
+		std::thread::sleep(Duration::from_millis(10));
+	}
+
+	// a child of java (left running in the background) may still hold the pipe open after java exits
+	let output = match read.recv_timeout(deadline.saturating_duration_since(std::time::Instant::now())) {
+		Ok(output) => output.map_err(Error::IoError)?,
+		Err(_) => {
+			log::debug!("\tjava's output was not closed within {:?}", timeout);
+			return Err(Error::JavaTimeout(timeout));
+		},
+	};
+	Ok(decode_output(output))
+}
+
+/// Decodes the output of a `java` process.
+///
+/// On Windows, the JVM writes to pipes in the system's ANSI codepage rather than UTF-8, so output that isn't valid UTF-8 is decoded with that codepage instead. This keeps non-ASCII paths (such as a `java.home` under a user's profile) intact. Elsewhere, invalid UTF-8 is replaced.
+fn decode_output(output: Vec<u8>) -> String {
+	#[cfg(target_os = "windows")]
+	{
+		match String::from_utf8(output) {
+			Ok(utf8) => utf8,
+			Err(e) => decode_ansi(e.as_bytes()).unwrap_or_else(|| String::from_utf8_lossy(e.as_bytes()).into_owned()),
+		}
+	}
+
+	#[cfg(not(target_os = "windows"))]
+	{
+		String::from_utf8_lossy(&output).into_owned()
+	}
+}
+
+/// Decodes bytes in the system's ANSI codepage with `MultiByteToWideChar`, returning `None` if they could not be decoded.
+#[cfg(target_os = "windows")]
+fn decode_ansi(bytes: &[u8]) -> Option<String> {
+	use std::convert::TryFrom;
+
+	const CP_ACP: u32 = 0;
+
+	#[link(name = "kernel32")]
+	extern "system" {
+		fn MultiByteToWideChar(code_page: u32, flags: u32, multi_byte: *const u8, multi_byte_len: i32, wide: *mut u16, wide_len: i32) -> i32;
+	}
+
+	let len = i32::try_from(bytes.len()).ok()?;
+	if len == 0 {
+		return Some(String::new());
+	}
+
+	// SAFETY: the input pointer and length describe `bytes`, and a null output with a zero length asks only for the required size
+	let wide_len = unsafe { MultiByteToWideChar(CP_ACP, 0, bytes.as_ptr(), len, std::ptr::null_mut(), 0) };
+	if wide_len <= 0 {
+		return None;
+	}
+
+	let mut wide = vec![0u16; wide_len as usize];
+	// SAFETY: `wide` has room for exactly `wide_len` characters, as requested above
+	let written = unsafe { MultiByteToWideChar(CP_ACP, 0, bytes.as_ptr(), len, wide.as_mut_ptr(), wide_len) };
+	if written <= 0 {
+		return None;
+	}
+	wide.truncate(written as usize);
+	Some(String::from_utf16_lossy(&wide))
+}
+
+/// Extracts the `java.home` property from the combined output of `java -XshowSettings:properties -version`.
+///
+/// Output without any content (besides whitespace) results in `Error::EmptyProbeOutput`, output rejecting the `-XshowSettings` option in `Error::ShowSettingsUnsupported`, and any other output without the property in `Error::NoJavaHomeProperty`.
+fn parse_java_home(output: &str) -> Result<PathBuf> {
+	if output.trim().is_empty() {
+		log::debug!("\tjava produced no output");
+		return Err(Error::EmptyProbeOutput);
+	}
+
+	// only the property itself, not other properties or banners that mention it (`Picked up JAVA_TOOL_OPTIONS: -Djava.home=...`)
+	let java_home = output.lines()
+		.filter(|line| !line.starts_with("Picked up "))
+		.find_map(|line| {
+			let (key, value) = line.split_once('=')?;
+			if key.trim() == "java.home" { Some(value.trim()) } else { None }
+		});
+
+	match java_home {
+		Some(path) => {
+			log::debug!("\tfound: {}", path);
+			Ok(PathBuf::from(path))
+		},
+		// JVMs before Java 7 reject the option outright
+		None if output.lines().any(|line| line.starts_with("Unrecognized option: -XshowSettings")) => {
+			log::debug!("\tjava does not support -XshowSettings");
+			Err(Error::ShowSettingsUnsupported)
+		},
+		None => {
+			log::debug!("\tnot found");
+			Err(Error::NoJavaHomeProperty)
+		},
+	}
+}
+
+/// Parses the `key = value` lines printed by `-XshowSettings:properties` into a map.
+///
+/// Multi-valued properties (such as `java.library.path`) are printed with each value on its own further-indented line, these are joined back together with the platform's path separator. Unindented lines (headers, version banners, etc) are ignored.
+fn parse_properties(output: &str) -> BTreeMap<String, String> {
+	const PATH_SEPARATOR: char = if cfg!(windows) { ';' } else { ':' };
+
+	let mut properties: BTreeMap<String, String> = BTreeMap::new();
+	// the current property, and its indentation
+	let mut current: Option<(String, usize)> = None;
+
+	for line in output.lines() {
+		let trimmed = line.trim_start();
+		let indent = line.len() - trimmed.len();
+		let trimmed = trimmed.trim_end();
+
+		if indent == 0 || trimmed.is_empty() {
+			current = None;
+			continue;
+		}
+
+		// continuation lines are further indented than their property
+		if let Some((key, key_indent)) = &current {
+			if indent > *key_indent {
+				if let Some(value) = properties.get_mut(key) {
+					if !value.is_empty() {
+						value.push(PATH_SEPARATOR);
+					}
+					value.push_str(trimmed);
+				}
+				continue;
+			}
+		}
+
+		current = match trimmed.split_once('=') {
+			Some((key, value)) if !key.trim_end().is_empty() && !key.trim_end().contains(char::is_whitespace) => {
+				let key = key.trim_end().to_string();
+				properties.insert(key.clone(), value.trim().to_string());
+				Some((key, indent))
+			},
+			_ => None,
+		};
+	}
+
+	properties
+}
+
+/// Parses the `KEY="value"` lines of an installation's `release` file. Values may or may not be quoted.
+fn parse_release(contents: &str) -> HashMap<String, String> {
+	contents.lines()
+		.filter_map(|line| line.split_once('='))
+		.map(|(key, value)| {
+			let value = value.trim();
+			let value = value.strip_prefix('"')
+				.and_then(|v| v.strip_suffix('"'))
+				.unwrap_or(value);
+			(key.trim().to_string(), value.to_string())
+		})
+		.collect()
+}
+
+/// Reads the home printed by MacOS's `/usr/libexec/java_home` tool, or its error message if it failed.
+#[cfg(target_os = "macos")]
+fn parse_macos_java_home(output: &std::process::Output) -> Result<JavaHome> {
+	let stdout = String::from_utf8_lossy(&output.stdout);
+	let path = stdout.lines().next().map(str::trim).unwrap_or("");
+	if !output.status.success() || path.is_empty() {
+		let message = String::from_utf8_lossy(&output.stderr).trim().to_string();
+		return Err(Error::MacosJavaHomeFailed(message));
+	}
+	Ok(JavaHome::assume(path))
+}
+
+/// Extracts the quoted version from a `java -version` banner, such as `17.0.2` from `openjdk version "17.0.2" 2022-01-18`.
+fn banner_version(banner: &str) -> Option<&str> {
+	banner.lines()
+		.filter(|line| line.contains(" version "))
+		.find_map(|line| {
+			let start = line.find('"')? + 1;
+			let len = line[start..].find('"')?;
+			Some(&line[start..start + len])
+		})
+}
+
+impl From<PathBuf> for JavaHome {
+	fn from(path: PathBuf) -> JavaHome {
+		JavaHome {
+			path,
+			#[cfg(feature = "glob")]
+			native_library: OnceLock::new(),
+		}
+	}
+}
+impl PartialEq for JavaHome {
+	fn eq(&self, other: &JavaHome) -> bool {
+		self.path == other.path
+	}
+}
+impl Eq for JavaHome {}
+impl PartialOrd for JavaHome {
+	fn partial_cmp(&self, other: &JavaHome) -> Option<std::cmp::Ordering> {
+		Some(self.cmp(other))
+	}
+}
+impl Ord for JavaHome {
+	fn cmp(&self, other: &JavaHome) -> std::cmp::Ordering {
+		self.path.cmp(&other.path)
+	}
+}
+impl std::hash::Hash for JavaHome {
+	fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+		self.path.hash(state);
+	}
+}
+impl Deref for JavaHome {
+	type Target = Path;
+	fn deref(&self) -> &Self::Target {
+		&self.path
+	}
+}
+/// Displays the home's path, as with `Path::display()` (so non-UTF-8 paths are displayed lossily).
+impl std::fmt::Display for JavaHome {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.path.display())
+	}
+}
+
+/// Serializes as the home's path, as a plain string.
+///
+/// Paths that are not valid UTF-8 are serialized as bytes instead - their raw bytes on Unix, and WTF-8 (UTF-8 extended to unpaired surrogates) on Windows - so they round-trip exactly.
+#[cfg(feature = "serde")]
+impl serde::Serialize for JavaHome {
+	fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+		if let Some(path) = self.path.to_str() {
+			return serializer.serialize_str(path);
+		}
+
+		#[cfg(unix)]
+		return {
+			use std::os::unix::ffi::OsStrExt;
+			serializer.serialize_bytes(self.path.as_os_str().as_bytes())
+		};
+		#[cfg(windows)]
+		return {
+			use std::os::windows::ffi::OsStrExt;
+			serializer.serialize_bytes(&encode_wtf8(self.path.as_os_str().encode_wide()))
+		};
+		#[cfg(not(any(unix, windows)))]
+		return Err(serde::ser::Error::custom("path is not valid UTF-8"));
+	}
+}
+
+/// Deserializes from a path string, or from the bytes that non-UTF-8 paths are serialized as.
+///
+/// The path is not checked, as with `JavaHome::assume()`.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for JavaHome {
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<JavaHome, D::Error> {
+		struct HomeVisitor;
+		impl<'de> serde::de::Visitor<'de> for HomeVisitor {
+			type Value = JavaHome;
+
+			fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+				f.write_str("a path string or bytes")
+			}
+
+			fn visit_str<E: serde::de::Error>(self, path: &str) -> std::result::Result<JavaHome, E> {
+				Ok(JavaHome::assume(path))
+			}
+
+			fn visit_bytes<E: serde::de::Error>(self, bytes: &[u8]) -> std::result::Result<JavaHome, E> {
+				#[cfg(unix)]
+				let path = {
+					use std::os::unix::ffi::OsStrExt;
+					OsStr::from_bytes(bytes).to_os_string()
+				};
+				#[cfg(windows)]
+				let path = {
+					use std::os::windows::ffi::OsStringExt;
+					let units = decode_wtf8(bytes).ok_or_else(|| E::invalid_value(serde::de::Unexpected::Bytes(bytes), &self))?;
+					OsString::from_wide(&units)
+				};
+				#[cfg(not(any(unix, windows)))]
+				let path = std::str::from_utf8(bytes).map_err(|_| E::invalid_value(serde::de::Unexpected::Bytes(bytes), &self))?;
+				Ok(JavaHome::assume(path))
+			}
+
+			// self-describing formats without a bytes type (such as JSON) write them as a sequence
+			fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> std::result::Result<JavaHome, A::Error> {
+				let mut bytes: Vec<u8> = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+				while let Some(byte) = seq.next_element()? {
+					bytes.push(byte);
+				}
+				self.visit_bytes(&bytes)
+			}
+		}
+
+		// strings and bytes share an encoding in non-self-describing formats (such as bincode), so either is read as bytes there
+		deserializer.deserialize_bytes(HomeVisitor)
+	}
+}
+
+/// Encodes UTF-16 code units as WTF-8, UTF-8 that also allows the unpaired surrogates found in some Windows paths.
+#[cfg(all(feature = "serde", any(windows, test)))]
+fn encode_wtf8(units: impl IntoIterator<Item = u16>) -> Vec<u8> {
+	let mut bytes = Vec::new();
+	for unit in std::char::decode_utf16(units) {
+		match unit {
+			Ok(c) => bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes()),
+			// encoded like any other code point of the range would be
+			Err(e) => {
+				let surrogate = e.unpaired_surrogate();
+				bytes.extend_from_slice(&[0xE0 | (surrogate >> 12) as u8, 0x80 | ((surrogate >> 6) & 0x3F) as u8, 0x80 | (surrogate & 0x3F) as u8]);
+			},
+		}
+	}
+	bytes
+}
+
+/// Decodes WTF-8 (see `encode_wtf8()`) back into UTF-16 code units, or `None` if the bytes are not WTF-8.
+#[cfg(all(feature = "serde", any(windows, test)))]
+fn decode_wtf8(mut bytes: &[u8]) -> Option<Vec<u16>> {
+	let mut units = Vec::new();
+	loop {
+		let (valid, rest) = match std::str::from_utf8(bytes) {
+			Ok(valid) => (valid, &[][..]),
+			Err(e) => (std::str::from_utf8(&bytes[..e.valid_up_to()]).ok()?, &bytes[e.valid_up_to()..]),
+		};
+		units.extend(valid.encode_utf16());
+
+		// surrogates are the only sequences WTF-8 allows that UTF-8 does not
+		match *rest {
+			[] => return Some(units),
+			[0xED, high @ 0xA0..=0xBF, low @ 0x80..=0xBF, ..] => {
+				units.push(0xD000 | (u16::from(high & 0x3F) << 6) | u16::from(low & 0x3F));
+				bytes = &rest[3..];
+			},
+			_ => return None,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Creates an empty file at `path` within `dir`, along with its parent folders.
+	#[cfg(feature = "glob")]
+	fn touch(dir: &Path, path: &str) {
+		let path = dir.join(path);
+		std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+		std::fs::write(path, "").unwrap();
+	}
+
+	#[test]
+	#[cfg(feature = "glob")]
+	fn native_library_prefers_server_variant() {
+		let dir = tempfile::tempdir().unwrap();
+		// the client folder sorts (and is found) first
+		touch(dir.path(), "lib/client/libjvm.so");
+		touch(dir.path(), "lib/server/libjvm.so");
+		let home = JavaHome::assume(dir.path());
+
+		let server = dir.path().join("lib").join("server").join("libjvm.so");
+		assert_eq!(home.native_library_for(TargetOs::Linux).unwrap(), server);
+		assert_eq!(home.find_native_library_named("libjvm.so").unwrap(), server);
+		#[cfg(target_os = "linux")]
+		assert_eq!(home.native_library().unwrap(), server);
+	}
+
+	/// Writes an executable shell script named `java` within `dir`.
+	#[cfg(unix)]
+	fn script(dir: &Path, body: &str) -> PathBuf {
+		use std::os::unix::fs::PermissionsExt;
+
+		let java = dir.join("java");
+		std::fs::write(&java, format!("#!/bin/sh\n{}", body)).unwrap();
+		std::fs::set_permissions(&java, std::fs::Permissions::from_mode(0o755)).unwrap();
+		java
+	}
+
+	#[test]
+	#[cfg(unix)]
+	fn output_held_open_by_a_grandchild_times_out() {
+		let dir = tempfile::tempdir().unwrap();
+		let java = script(dir.path(), "sleep 30 &\nexit 0\n");
+
+		let started = std::time::Instant::now();
+		let result = run_command_within(Command::new(java), Some(Duration::from_millis(500)));
+		assert!(matches!(result, Err(Error::JavaTimeout(_))), "{:?}", result);
+		assert!(started.elapsed() < Duration::from_secs(10));
+	}
+
+	#[test]
+	fn empty_output_is_reported() {
+		assert!(matches!(parse_java_home(""), Err(Error::EmptyProbeOutput)));
+		assert!(matches!(parse_java_home("  \n"), Err(Error::EmptyProbeOutput)));
+	}
+
+	#[test]
+	fn java_home_is_parsed_from_probe_output() {
+		let samples = [
+			// the banner mentions the property before the real one
+			"Picked up JAVA_TOOL_OPTIONS: -Djava.home=/wrong\nProperty settings:\n    java.class.path = \n    java.home = /opt/jdk\n    java.version = 17.0.2\n",
+			// a localized header, with properties in a different order
+			"Eigenschaftseinstellungen:\n    user.language = de\n    java.home = /opt/jdk\n    file.encoding = UTF-8\n\nopenjdk version \"17.0.2\"\n",
+			// a property whose value contains `java.home=` before the real one
+			"Property settings:\n    jdk.debug = -Djava.home=/wrong\n    sun.java.command = Main java.home=/wrong\n    java.home = /opt/jdk\n",
+		];
+		for sample in &samples {
+			assert_eq!(parse_java_home(sample).unwrap(), Path::new("/opt/jdk"), "{}", sample);
+		}
+
+		assert!(matches!(parse_java_home("Picked up JAVA_TOOL_OPTIONS: -Djava.home=/wrong\n"), Err(Error::NoJavaHomeProperty)));
+		assert!(matches!(parse_java_home("Unrecognized option: -XshowSettings:properties\n"), Err(Error::ShowSettingsUnsupported)));
+	}
+
+	#[test]
+	#[cfg(unix)]
+	fn active_home_is_read_from_stderr() {
+		// the fixture's java only writes to stderr, like a real one
+		let jdk = test_fixtures::fake_jdk(17, RuntimeKind::Jdk);
+		let home = JavaHome::find_active_home_from(jdk.path().join("bin").join("java")).unwrap();
+		assert_eq!(home.path, jdk.path());
+	}
+
+	#[test]
+	fn checked_corrects_java_executable_to_home() {
+		let jdk = test_fixtures::fake_jdk(17, RuntimeKind::Jdk);
+		let java = JavaHome::assume(jdk.path()).java_executable();
+		assert_eq!(JavaHome::checked(java).unwrap().path, jdk.path());
+
+		let release = jdk.path().join("release");
+		assert!(matches!(JavaHome::checked(&release), Err(Error::BadJavaHomePath(path)) if path == release));
+	}
+
+	#[test]
+	fn env_home_without_java_falls_back() {
+		let dir = tempfile::tempdir().unwrap();
+		let reason = JavaHome::env_home_at(dir.path().to_path_buf()).unwrap().unwrap_err();
+		assert_eq!(reason, FallbackReason::EnvVarNoJava(dir.path().to_path_buf()));
+	}
+
+	#[test]
+	fn multi_line_properties_are_joined() {
+		let output = "Property settings:\n    java.home = /opt/jdk\n    java.library.path = /usr/java/packages/lib\n        /usr/lib64\n        /lib64\n    java.version = 17.0.2\n\nopenjdk version \"17.0.2\"\n";
+		let properties = parse_properties(output);
+
+		let separator = if cfg!(windows) { ";" } else { ":" };
+		assert_eq!(properties["java.library.path"], ["/usr/java/packages/lib", "/usr/lib64", "/lib64"].join(separator));
+		assert_eq!(properties["java.home"], "/opt/jdk");
+		assert_eq!(properties["java.version"], "17.0.2");
+		assert_eq!(properties.len(), 3);
+	}
+
+	#[test]
+	fn flags_are_parsed_from_each_format() {
+		let output = "[Global flags]\n\
+			\x20    bool UseCompressedOops                        := true                                {lp64_product}\n\
+			\x20   ccstr AbortVMOnException                        =                                     {diagnostic}\n\
+			\x20    uint MaxHeapFreeRatio                          = 70                                     {manageable} {default}\n\
+			openjdk version \"1.8.0_292\"\n";
+		let flags = parse_flags_final(output);
+
+		assert_eq!(flags["UseCompressedOops"], "true");
+		assert_eq!(flags["AbortVMOnException"], "");
+		assert_eq!(flags["MaxHeapFreeRatio"], "70");
+		assert_eq!(flags.len(), 3);
+	}
+
+	#[test]
+	fn release_values_are_unquoted() {
+		let release = parse_release("IMPLEMENTOR=\"Eclipse Adoptium\"\nJAVA_VERSION=\"17.0.2\"\nOS_ARCH=x86_64\nMODULES=\"java.base java.logging\"\n\n");
+
+		assert_eq!(release["IMPLEMENTOR"], "Eclipse Adoptium");
+		assert_eq!(release["JAVA_VERSION"], "17.0.2");
+		assert_eq!(release["OS_ARCH"], "x86_64");
+		assert_eq!(release["MODULES"], "java.base java.logging");
+		assert_eq!(release.len(), 4);
+	}
+
+	#[test]
+	#[cfg(unix)]
+	fn fixture_reports_class_version_and_runs_javac() {
+		let jdk = test_fixtures::fake_jdk(8, RuntimeKind::Jdk);
+		let home = JavaHome::assume(jdk.path());
+		assert_eq!(home.class_version().unwrap(), (52, 0));
+
+		let javac = Command::new(jdk.path().join("bin").join("javac")).arg("-version").output().unwrap();
+		assert_eq!(String::from_utf8_lossy(&javac.stdout).trim(), "javac 1.8.0");
+	}
+
+	#[test]
+	fn too_old_version_is_rejected() {
+		let jdk = test_fixtures::fake_jdk(11, RuntimeKind::Jdk);
+		let home = JavaHome::assume(jdk.path());
+
+		assert_eq!(home.clone().require_feature(11).unwrap(), home);
+		match home.require_feature(17) {
+			Err(Error::VersionTooOld { found, required }) => {
+				assert_eq!(found.major, 11);
+				assert_eq!(required, 17);
+			},
+			other => panic!("expected VersionTooOld, got {:?}", other),
+		}
+	}
+
+	#[test]
+	#[cfg(unix)]
+	fn only_dangling_symlinks_are_reported_as_broken() {
+		let dir = tempfile::tempdir().unwrap();
+		let notes = dir.path().join("notes.txt");
+		std::fs::write(&notes, "").unwrap();
+
+		let dangling = dir.path().join("dangling");
+		std::os::unix::fs::symlink(dir.path().join("removed-jdk"), &dangling).unwrap();
+		let reason = JavaHome::env_home_at(dangling.clone()).unwrap().unwrap_err();
+		assert_eq!(reason, FallbackReason::EnvVarBrokenSymlink(dangling));
+
+		let to_file = dir.path().join("to-file");
+		std::os::unix::fs::symlink(&notes, &to_file).unwrap();
+		let reason = JavaHome::env_home_at(to_file.clone()).unwrap().unwrap_err();
+		assert_eq!(reason, FallbackReason::EnvVarBadPath(to_file));
+	}
+
+	#[test]
+	#[cfg(unix)]
+	fn failed_flags_query_is_an_error() {
+		let dir = tempfile::tempdir().unwrap();
+		std::fs::create_dir(dir.path().join("bin")).unwrap();
+		script(&dir.path().join("bin"), "echo 'Error: Could not create the Java Virtual Machine.' >&2\nexit 1\n");
+
+		let flags = JavaHome::assume(dir.path()).default_flags();
+		assert!(matches!(flags, Err(Error::NoFlagsInformation)), "{:?}", flags);
+	}
+
+	#[test]
+	fn validate_requires_java() {
+		let dir = tempfile::tempdir().unwrap();
+		assert!(matches!(JavaHome::validate(dir.path()), Err(Error::BadJavaHomePath(path)) if path == dir.path()));
+
+		let jdk = test_fixtures::fake_jdk(17, RuntimeKind::Jre);
+		assert_eq!(JavaHome::validate(jdk.path()).unwrap().path, jdk.path());
+	}
+
+	#[test]
+	#[cfg(feature = "serde")]
+	fn serde_round_trips() {
+		let mut homes = vec![JavaHome::assume("/opt/jdk-17")];
+		#[cfg(unix)]
+		{
+			use std::os::unix::ffi::OsStrExt;
+			homes.push(JavaHome::assume(OsStr::from_bytes(b"/opt/jdk-\xff")));
+		}
+		#[cfg(windows)]
+		{
+			use std::os::windows::ffi::OsStringExt;
+			homes.push(JavaHome::assume(OsString::from_wide(&[0x43, 0x3A, 0x5C, 0xD800])));
+		}
+
+		for home in &homes {
+			let json = serde_json::to_string(home).unwrap();
+			assert_eq!(&serde_json::from_str::<JavaHome>(&json).unwrap(), home, "{}", json);
+
+			let encoded = bincode::serialize(home).unwrap();
+			assert_eq!(&bincode::deserialize::<JavaHome>(&encoded).unwrap(), home);
+		}
+		assert_eq!(serde_json::to_string(&homes[0]).unwrap(), "\"/opt/jdk-17\"");
+	}
+
+	#[test]
+	#[cfg(feature = "serde")]
+	fn wtf8_round_trips_unpaired_surrogates() {
+		// `a`, an unpaired high surrogate, a surrogate pair (U+1F600), and an unpaired low surrogate
+		let units = [0x61, 0xD800, 0xD83D, 0xDE00, 0xDC00];
+		let bytes = encode_wtf8(units.iter().copied());
+		assert_eq!(bytes, b"a\xED\xA0\x80\xF0\x9F\x98\x80\xED\xB0\x80");
+		assert_eq!(decode_wtf8(&bytes).unwrap(), units);
+
+		assert_eq!(decode_wtf8(b"C:\\jdk").unwrap(), "C:\\jdk".encode_utf16().collect::<Vec<_>>());
+		assert_eq!(decode_wtf8(b"\xff"), None);
+	}
+
+	#[test]
+	#[cfg(unix)]
+	fn dangling_module_links_are_not_listed() {
+		let jdk = test_fixtures::fake_jdk(17, RuntimeKind::Jdk);
+		std::fs::remove_dir_all(jdk.path().join("jmods")).unwrap();
+		std::os::unix::fs::symlink(jdk.path().join("removed"), jdk.path().join("jmods")).unwrap();
+
+		let home = JavaHome::assume(jdk.path());
+		assert_eq!(home.default_module_path().unwrap(), vec![jdk.path().join("lib").join("modules")]);
 	}
 }
@@ -0,0 +1,137 @@
+//! Minimal Mach-O parsing, for inspecting the linked dylibs of MacOS native libraries without external tools.
+
+use std::convert::{TryFrom, TryInto};
+
+const MH_MAGIC: u32 = 0xfeed_face;
+const MH_MAGIC_64: u32 = 0xfeed_facf;
+const FAT_MAGIC: u32 = 0xcafe_babe;
+/// The size of a fat header and its first architecture entry, before which no architecture's file can start.
+const FAT_FIRST_ARCH_END: usize = 8 + 20;
+
+const LC_LOAD_DYLIB: u32 = 0xc;
+const LC_LOAD_WEAK_DYLIB: u32 = 0x8000_0018;
+const LC_REEXPORT_DYLIB: u32 = 0x8000_001f;
+const LC_LAZY_LOAD_DYLIB: u32 = 0x20;
+const LC_LOAD_UPWARD_DYLIB: u32 = 0x8000_0023;
+
+/// Reads little or big endian integers from a Mach-O file.
+struct Reader<'a> {
+	data: &'a [u8],
+	is_le: bool,
+}
+impl<'a> Reader<'a> {
+	fn bytes<const N: usize>(&self, offset: usize) -> Option<[u8; N]> {
+		self.data.get(offset..offset.checked_add(N)?)?.try_into().ok()
+	}
+	fn u32(&self, offset: usize) -> Option<u32> {
+		let b = self.bytes(offset)?;
+		Some(if self.is_le { u32::from_le_bytes(b) } else { u32::from_be_bytes(b) })
+	}
+	fn cstr(&self, offset: usize, end: usize) -> Option<&'a str> {
+		let tail = self.data.get(offset..end)?;
+		let len = tail.iter().position(|&b| b == 0).unwrap_or(tail.len());
+		std::str::from_utf8(&tail[..len]).ok()
+	}
+}
+
+/// Returns the install names of the dylibs a Mach-O file links to (such as `@rpath/libjli.dylib` or `/usr/lib/libSystem.B.dylib`), or `None` if the data is not a well-formed Mach-O file.
+///
+/// For universal (fat) files, only the first architecture is inspected - the architectures of a JDK's libraries link to the same dylibs.
+pub(crate) fn needed_libraries(data: &[u8]) -> Option<Vec<String>> {
+	// fat headers are always big endian, with the first architecture's offset after the magic, count, cputype, and cpusubtype
+	if u32::from_be_bytes(data.get(..4)?.try_into().ok()?) == FAT_MAGIC {
+		let offset = usize::try_from(u32::from_be_bytes(data.get(16..20)?.try_into().ok()?)).ok()?;
+		// an offset within the header would point back at (or into) the fat header itself
+		if offset < FAT_FIRST_ARCH_END {
+			return None;
+		}
+		return thin_needed_libraries(data.get(offset..)?);
+	}
+
+	thin_needed_libraries(data)
+}
+
+/// Like `needed_libraries()`, for a single architecture's Mach-O file. Fat files nested within a fat file are not accepted.
+fn thin_needed_libraries(data: &[u8]) -> Option<Vec<String>> {
+	let (is_le, is_64) = match (u32::from_le_bytes(data.get(..4)?.try_into().ok()?), u32::from_be_bytes(data.get(..4)?.try_into().ok()?)) {
+		(MH_MAGIC, _) => (true, false),
+		(MH_MAGIC_64, _) => (true, true),
+		(_, MH_MAGIC) => (false, false),
+		(_, MH_MAGIC_64) => (false, true),
+		_ => return None,
+	};
+	let r = Reader { data, is_le };
+
+	let command_count = r.u32(16)?;
+	let mut command = if is_64 { 32 } else { 28 };
+
+	let mut needed = Vec::new();
+	for _ in 0..command_count {
+		let kind = r.u32(command)?;
+		let size = usize::try_from(r.u32(command.checked_add(4)?)?).ok()?;
+		if size < 8 {
+			return None;
+		}
+
+		match kind {
+			LC_LOAD_DYLIB | LC_LOAD_WEAK_DYLIB | LC_REEXPORT_DYLIB | LC_LAZY_LOAD_DYLIB | LC_LOAD_UPWARD_DYLIB => {
+				// the name is stored within the command, at an offset from its start
+				let name = usize::try_from(r.u32(command.checked_add(8)?)?).ok()?;
+				needed.push(r.cstr(command.checked_add(name)?, command.checked_add(size)?)?.to_string());
+			},
+			_ => {},
+		}
+		command = command.checked_add(size)?;
+	}
+
+	Some(needed)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn fat_header_pointing_at_itself_is_rejected() {
+		// a fat header whose first architecture starts at offset 0, padded to 36 bytes
+		let mut data = vec![0u8; 36];
+		data[..4].copy_from_slice(&FAT_MAGIC.to_be_bytes());
+		data[4..8].copy_from_slice(&1u32.to_be_bytes());
+		assert_eq!(needed_libraries(&data), None);
+
+		// pointing into the header, or at a nested fat header, is rejected as well
+		data[16..20].copy_from_slice(&8u32.to_be_bytes());
+		assert_eq!(needed_libraries(&data), None);
+		data[16..20].copy_from_slice(&28u32.to_be_bytes());
+		data[28..32].copy_from_slice(&FAT_MAGIC.to_be_bytes());
+		assert_eq!(needed_libraries(&data), None);
+	}
+
+	#[test]
+	fn malformed_load_commands_are_rejected() {
+		// a 64-bit little endian header with one load command
+		let mut data = vec![0u8; 32];
+		data[..4].copy_from_slice(&MH_MAGIC_64.to_le_bytes());
+		data[16..20].copy_from_slice(&1u32.to_le_bytes());
+		// the command is missing
+		assert_eq!(needed_libraries(&data), None);
+
+		// a command too small to hold its own header
+		data.extend_from_slice(&LC_LOAD_DYLIB.to_le_bytes());
+		data.extend_from_slice(&4u32.to_le_bytes());
+		assert_eq!(needed_libraries(&data), None);
+
+		// a name offset past the end of the command
+		data[36..40].copy_from_slice(&16u32.to_le_bytes());
+		data.extend_from_slice(&u32::MAX.to_le_bytes());
+		data.extend_from_slice(&[0u8; 4]);
+		assert_eq!(needed_libraries(&data), None);
+
+		// and a well-formed one
+		data[40..44].copy_from_slice(&12u32.to_le_bytes());
+		data[36..40].copy_from_slice(&20u32.to_le_bytes());
+		data.truncate(44);
+		data.extend_from_slice(b"libz\0\0\0\0");
+		assert_eq!(needed_libraries(&data), Some(vec!["libz".to_string()]));
+	}
+}
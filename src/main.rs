@@ -2,9 +2,9 @@
 use jvm_find::JavaHome;
 
 fn main() -> Result<(), jvm_find::Error> {
-	println!("find_home(): {}", JavaHome::find_home()?.display());
-	println!("find_active_home(): {}", JavaHome::find_active_home()?.display());
-	println!("find_valid_home(): {}", JavaHome::find_valid_home()?.display());
+	println!("find_home(): {}", JavaHome::find_home()?);
+	println!("find_active_home(): {}", JavaHome::find_active_home()?);
+	println!("find_valid_home(): {}", JavaHome::find_valid_home()?);
 
 	let home = JavaHome::find_home().unwrap();
 
@@ -0,0 +1,136 @@
+//! Async variants of the finder functions, for use within a tokio runtime.
+
+use std::ffi::OsStr;
+use std::time::Duration;
+
+use tokio::process::Command;
+
+use crate::{decode_output, parse_java_home, spawn_error, Error, JavaHome, Result};
+
+impl JavaHome {
+	/// Like `JavaHome::find_home()`, but awaits the `java` executable (if JAVA_HOME is unset) rather than blocking on it.
+	///
+	/// # Errors
+	/// This function will error in any situation that `JavaHome::find_active_home_async()` will.
+	pub async fn find_home_async() -> Result<Self> {
+		match std::env::var_os(JavaHome::ENV_VAR).filter(|var| !var.is_empty()) {
+			Some(path) => Ok(JavaHome::assume(path)),
+			None => JavaHome::find_platform_home_async().await,
+		}
+	}
+
+	/// Like `JavaHome::find_valid_home()`, but checks the JAVA_HOME environment variable on a blocking thread (as `tokio::fs` does), and awaits the `java` executable rather than blocking on it. JAVA_HOME is accepted and corrected in the same way.
+	///
+	/// # Errors
+	/// This will error if the directory specified by JAVA_HOME, or its `bin/java`, is unreachable (due to permissions/broken links/etc errors), or in any situation that `JavaHome::find_active_home_async()` will.
+	pub async fn find_valid_home_async() -> Result<Self> {
+		let env_home = tokio::task::spawn_blocking(JavaHome::env_home).await
+			.map_err(|e| Error::IoError(e.into()))?;
+		match env_home? {
+			Ok(home) => Ok(home),
+			Err(reason) => {
+				log::debug!("JAVA_HOME not usable ({}), falling back to the active java installation", reason);
+				JavaHome::find_active_home_async().await
+			},
+		}
+	}
+
+	/// Like `JavaHome::find_active_home()`, but spawns and awaits the `java` executable with `tokio::process`, rather than blocking the calling thread.
+	///
+	/// The executable is given `JavaHome::DEFAULT_TIMEOUT` to respond, and is killed if it does not.
+	///
+	/// # Errors
+	/// This function will error in any situation that `JavaHome::find_active_home()` will.
+	pub async fn find_active_home_async() -> Result<Self> {
+		log::debug!("finding currently active JAVA_HOME location by running the `java` command from the system path");
+		let output = run_combined_async("java", &["-XshowSettings:properties", "-version"], JavaHome::DEFAULT_TIMEOUT).await?;
+		parse_java_home(&output).map(JavaHome::assume)
+	}
+
+	/// Like `JavaHome::find_platform_home()`, awaiting any subprocesses.
+	async fn find_platform_home_async() -> Result<Self> {
+		#[cfg(target_os = "macos")]
+		match macos_java_home_async().await {
+			Ok(home) => return Ok(home),
+			Err(e) => log::debug!("/usr/libexec/java_home failed ({}), running java instead", e),
+		}
+
+		JavaHome::find_active_home_async().await
+	}
+}
+
+/// Like `JavaHome::find_macos_java_home(None)`, awaiting the tool rather than blocking on it.
+#[cfg(target_os = "macos")]
+async fn macos_java_home_async() -> Result<JavaHome> {
+	let output = Command::new("/usr/libexec/java_home").output().await.map_err(Error::JavaExecution)?;
+	crate::parse_macos_java_home(&output)
+}
+
+/// Like `run_combined_within()`, but awaits the process. The child is killed if it does not exit within the timeout.
+///
+/// Standard output and error are captured separately and joined, rather than interleaved - the callers only search the output for lines, not their order.
+async fn run_combined_async(java: impl AsRef<OsStr>, args: &[&str], timeout: Duration) -> Result<String> {
+	let mut command = Command::new(java);
+	command.args(args)
+		.stdin(std::process::Stdio::null())
+		// dropping the output future (on timeout) kills the child
+		.kill_on_drop(true);
+	log::debug!("running {:?}", command);
+
+	let output = match tokio::time::timeout(timeout, command.output()).await {
+		Ok(output) => output.map_err(spawn_error)?,
+		Err(_) => {
+			log::debug!("\tjava did not exit within {:?}, killing it", timeout);
+			return Err(Error::JavaTimeout(timeout));
+		},
+	};
+
+	let mut combined = output.stdout;
+	combined.extend_from_slice(&output.stderr);
+	Ok(decode_output(combined))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{test_fixtures, RuntimeKind};
+
+	fn runtime() -> tokio::runtime::Runtime {
+		tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap()
+	}
+
+	#[test]
+	#[cfg(unix)]
+	fn java_is_probed_asynchronously() {
+		let jdk = test_fixtures::fake_jdk(17, RuntimeKind::Jdk);
+		let java = JavaHome::assume(jdk.path()).java_executable();
+
+		let output = runtime().block_on(run_combined_async(java, &["-XshowSettings:properties", "-version"], JavaHome::DEFAULT_TIMEOUT)).unwrap();
+		assert_eq!(parse_java_home(&output).unwrap(), jdk.path());
+	}
+
+	#[test]
+	#[cfg(unix)]
+	fn hung_java_times_out() {
+		use std::os::unix::fs::PermissionsExt;
+
+		let dir = tempfile::tempdir().unwrap();
+		let java = dir.path().join("java");
+		std::fs::write(&java, "#!/bin/sh\nsleep 30\n").unwrap();
+		std::fs::set_permissions(&java, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+		let output = runtime().block_on(run_combined_async(java, &["-version"], Duration::from_millis(200)));
+		assert!(matches!(output, Err(Error::JavaTimeout(_))), "{:?}", output);
+	}
+
+	#[test]
+	fn java_home_pointing_at_java_is_corrected() {
+		let jdk = test_fixtures::fake_jdk(17, RuntimeKind::Jdk);
+		// the only test reading JAVA_HOME, so setting it here can't race with another
+		std::env::set_var(JavaHome::ENV_VAR, JavaHome::assume(jdk.path()).java_executable());
+
+		let home = runtime().block_on(JavaHome::find_valid_home_async());
+		std::env::remove_var(JavaHome::ENV_VAR);
+		assert_eq!(home.unwrap().path, jdk.path());
+	}
+}
@@ -0,0 +1,135 @@
+//! Minimal PE parsing, for inspecting the imported DLLs of Windows native libraries without external tools.
+
+use std::convert::{TryFrom, TryInto};
+
+const PE32_MAGIC: u16 = 0x10b;
+const PE32_PLUS_MAGIC: u16 = 0x20b;
+const IMPORT_DIRECTORY: usize = 1;
+
+/// Reads little endian integers from a PE file.
+struct Reader<'a> {
+	data: &'a [u8],
+}
+impl<'a> Reader<'a> {
+	fn bytes<const N: usize>(&self, offset: usize) -> Option<[u8; N]> {
+		self.data.get(offset..offset.checked_add(N)?)?.try_into().ok()
+	}
+	fn u16(&self, offset: usize) -> Option<u16> {
+		self.bytes(offset).map(u16::from_le_bytes)
+	}
+	fn u32(&self, offset: usize) -> Option<u32> {
+		self.bytes(offset).map(u32::from_le_bytes)
+	}
+	fn cstr(&self, offset: usize) -> Option<&'a str> {
+		let tail = self.data.get(offset..)?;
+		let len = tail.iter().position(|&b| b == 0)?;
+		std::str::from_utf8(&tail[..len]).ok()
+	}
+}
+
+/// A section header's virtual address and size, and where its data is within the file.
+struct Section {
+	vaddr: u32,
+	vsize: u32,
+	raw_offset: u32,
+}
+
+/// Returns the names of the DLLs imported by a PE file (such as `KERNEL32.dll`), or `None` if the data is not a well-formed PE file.
+///
+/// Delay-loaded imports are not included. Files without an import directory have no dependencies.
+pub(crate) fn needed_libraries(data: &[u8]) -> Option<Vec<String>> {
+	if data.get(..2)? != b"MZ" {
+		return None;
+	}
+	let r = Reader { data };
+
+	let pe = usize::try_from(r.u32(0x3C)?).ok()?;
+	if data.get(pe..pe.checked_add(4)?)? != b"PE\0\0" {
+		return None;
+	}
+	let coff = pe + 4;
+	let section_count = r.u16(coff + 2)?;
+	let optional = coff + 20;
+	let optional_size = r.u16(coff + 16)?;
+
+	// the data directories follow the (differently sized) standard and windows-specific fields
+	let directories = match r.u16(optional)? {
+		PE32_MAGIC => optional + 96,
+		PE32_PLUS_MAGIC => optional + 112,
+		_ => return None,
+	};
+	let directory_count = usize::try_from(r.u32(directories - 4)?).ok()?;
+	if directory_count <= IMPORT_DIRECTORY {
+		return Some(Vec::new());
+	}
+	let imports_rva = r.u32(directories + IMPORT_DIRECTORY * 8)?;
+	if imports_rva == 0 {
+		return Some(Vec::new());
+	}
+
+	let sections_start = optional + usize::from(optional_size);
+	let sections = (0..usize::from(section_count))
+		.map(|i| {
+			let base = sections_start + i * 40;
+			Some(Section { vsize: r.u32(base + 8)?, vaddr: r.u32(base + 12)?, raw_offset: r.u32(base + 20)? })
+		})
+		.collect::<Option<Vec<Section>>>()?;
+
+	// RVAs are addresses once loaded, find where the section containing each was read from
+	let offset_of = |rva: u32| -> Option<usize> {
+		let section = sections.iter().find(|s| s.vaddr <= rva && rva < s.vaddr.saturating_add(s.vsize.max(1)))?;
+		usize::try_from((rva - section.vaddr).checked_add(section.raw_offset)?).ok()
+	};
+
+	// import descriptors are 20 bytes each, ending with an all-zero descriptor
+	let start = offset_of(imports_rva)?;
+	let mut needed = Vec::new();
+	for i in 0.. {
+		let descriptor = start + i * 20;
+		let name_rva = r.u32(descriptor + 12)?;
+		if name_rva == 0 {
+			break;
+		}
+		needed.push(r.cstr(offset_of(name_rva)?)?.to_string());
+	}
+
+	Some(needed)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// A DOS header pointing at a PE signature, followed by a COFF header and the optional header's magic.
+	fn header(magic: u16) -> Vec<u8> {
+		let mut data = vec![0u8; 0x40];
+		data[..2].copy_from_slice(b"MZ");
+		data[0x3C..0x40].copy_from_slice(&0x40u32.to_le_bytes());
+		data.extend_from_slice(b"PE\0\0");
+		data.extend_from_slice(&[0u8; 20]);
+		data.extend_from_slice(&magic.to_le_bytes());
+		data
+	}
+
+	#[test]
+	fn malformed_files_are_rejected() {
+		// no PE signature where the DOS header points
+		let mut no_signature = vec![0u8; 0x40];
+		no_signature[..2].copy_from_slice(b"MZ");
+		let mut past_end = header(PE32_PLUS_MAGIC);
+		past_end[0x3C..0x40].copy_from_slice(&u32::MAX.to_le_bytes());
+
+		let samples: Vec<Vec<u8>> = vec![
+			Vec::new(),
+			b"\x7fELF".to_vec(),
+			no_signature,
+			past_end,
+			header(0x1234),
+			// truncated within the optional header
+			header(PE32_MAGIC),
+		];
+		for data in &samples {
+			assert_eq!(needed_libraries(data), None, "{:?}", data);
+		}
+	}
+}
@@ -0,0 +1,40 @@
+use std::path::PathBuf;
+
+use crate::{JavaHome, JavaVersion};
+
+/// The commonly needed details of a Java installation, gathered at once by `JavaHome::summary()`.
+///
+/// With the `serde` feature, this can be serialized to cache the details between runs instead of probing the installation again.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct JavaSummary {
+	/// The installation's home directory
+	pub path: PathBuf,
+	/// The JVM's version, from the `java.version` property
+	pub version: JavaVersion,
+	/// The JVM's vendor (such as `Eclipse Adoptium`), from the `java.vendor` property
+	pub vendor: Option<String>,
+	/// The unparsed architecture the JVM was built for (such as `amd64` or `aarch64`), from the `os.arch` property
+	pub arch: String,
+	/// Whether the installation is a JDK, having a `javac` executable
+	pub is_jdk: bool,
+	/// The native library's path relative to `path`, if one was found
+	#[cfg(feature = "glob")]
+	pub native_library: Option<PathBuf>,
+}
+
+/// A Java installation found by `JavaHome::survey()`, with the details gathered about it.
+///
+/// Details that could not be determined (such as when the installation's `java` fails to run) are left as `None`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct JavaInstallation {
+	/// The installation's home directory
+	pub home: JavaHome,
+	/// The JVM's version, from the `java.version` property
+	pub version: Option<JavaVersion>,
+	/// The JVM's vendor (such as `Eclipse Adoptium`), from the `java.vendor` property
+	pub vendor: Option<String>,
+	/// Whether the installation is a JDK, having a `javac` executable
+	pub is_jdk: bool,
+}
@@ -0,0 +1,155 @@
+//! Helpers for testing code that consumes this crate, without depending on the Java installations of the machine running the tests.
+
+use std::fs;
+use std::path::Path;
+
+use crate::RuntimeKind;
+
+/// Creates a temporary directory containing a minimal, structurally valid Java installation of the given feature version and kind.
+///
+/// The layout follows the distributions of that version - JDK 8 and earlier nest a `jre/` folder inside the JDK and ship `lib/tools.jar`, while later versions ship `lib/modules` (and `jmods/` for a JDK). All installations have a `release` file, a `bin/java` executable, and a placeholder (empty) native library. A JDK additionally has `bin/javac` and the JNI headers under `include/`.
+///
+/// On Unix platforms, `bin/java` is a shell script answering `-version` and `-XshowSettings:properties` like a real JVM would, reporting the fixture's runtime directory as `java.home` (the nested `jre/` folder for a legacy JDK, like a real one would) and the version's `java.class.version`. A JDK's `bin/javac` is likewise an executable script, printing its version. Other platforms get empty `java.exe` and `javac.exe` placeholders, so functions probing the JVM will fail against them.
+///
+/// The installation is deleted when the returned `TempDir` is dropped. Point a `JavaHome` at it with `JavaHome::assume(dir.path())`.
+///
+/// # Panics
+/// This function panics if the temporary directory or any of its contents could not be created.
+pub fn fake_jdk(version: u32, kind: RuntimeKind) -> tempfile::TempDir {
+	let dir = tempfile::Builder::new()
+		.prefix("jvm-find-fixture-")
+		.tempdir()
+		.expect("unable to create temporary directory for fake JDK");
+	let home = dir.path();
+
+	let version_string = if version <= 8 {
+		format!("1.{}.0", version)
+	} else {
+		version.to_string()
+	};
+
+	write(home, "release", &format!(
+		"IMPLEMENTOR=\"jvm-find\"\nJAVA_VERSION=\"{}\"\nOS_ARCH=\"{}\"\n",
+		version_string, std::env::consts::ARCH,
+	));
+
+	// legacy JDKs nest the runtime within a `jre` folder, with the native library inside it
+	let runtime = if version <= 8 && kind == RuntimeKind::Jdk {
+		home.join("jre")
+	} else {
+		home.to_path_buf()
+	};
+
+	java_stub(&runtime, &runtime, version, &version_string);
+	if runtime != home {
+		java_stub(home, &runtime, version, &version_string);
+	}
+
+	#[cfg(target_os = "windows")]
+	let native_dir = runtime.join("bin").join("server");
+	#[cfg(not(target_os = "windows"))]
+	let native_dir = runtime.join("lib").join("server");
+	write(&native_dir, crate::NATIVE_LIBRARY_FILENAME, "");
+
+	if version <= 8 {
+		if kind == RuntimeKind::Jdk {
+			write(&home.join("lib"), "tools.jar", "");
+		}
+	} else {
+		write(&home.join("lib"), "modules", "");
+		if kind == RuntimeKind::Jdk {
+			write(&home.join("jmods"), "java.base.jmod", "");
+		}
+	}
+
+	if kind == RuntimeKind::Jdk {
+		javac_stub(home, &version_string);
+
+		#[cfg(target_os = "windows")]
+		let platform = "win32";
+		#[cfg(target_os = "macos")]
+		let platform = "darwin";
+		#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+		let platform = std::env::consts::OS;
+
+		write(&home.join("include"), "jni.h", "");
+		write(&home.join("include").join(platform), "jni_md.h", "");
+	}
+
+	dir
+}
+
+/// Creates `bin/java` within `dir`, reporting `home` as its `java.home`.
+fn java_stub(dir: &Path, home: &Path, feature: u32, version: &str) {
+	let bin = dir.join("bin");
+
+	#[cfg(unix)]
+	{
+		use std::os::unix::fs::PermissionsExt;
+
+		let script = format!(
+			"#!/bin/sh\n\
+			for arg in \"$@\"; do\n\
+			\tif [ \"$arg\" = \"-XshowSettings:properties\" ]; then\n\
+			\t\tcat >&2 <<'END_PROPERTIES'\n\
+			Property settings:\n\
+			\x20   java.class.version = {class_version}.0\n\
+			\x20   java.home = {home}\n\
+			\x20   java.vendor = jvm-find\n\
+			\x20   java.version = {version}\n\
+			\x20   os.arch = {arch}\n\
+			\x20   sun.arch.data.model = {bits}\n\
+			\n\
+			END_PROPERTIES\n\
+			\tfi\n\
+			done\n\
+			echo 'openjdk version \"{version}\"' >&2\n\
+			echo 'OpenJDK {vm}Server VM (build {version}, mixed mode)' >&2\n",
+			class_version = 44 + feature,
+			home = home.display(),
+			version = version,
+			arch = std::env::consts::ARCH,
+			bits = if cfg!(target_pointer_width = "64") { 64 } else { 32 },
+			vm = if cfg!(target_pointer_width = "64") { "64-Bit " } else { "" },
+		);
+		write(&bin, "java", &script);
+
+		let java = bin.join("java");
+		fs::set_permissions(&java, fs::Permissions::from_mode(0o755))
+			.expect("unable to mark fake JDK's java executable as executable");
+	}
+
+	#[cfg(not(unix))]
+	{
+		let _ = (home, feature, version);
+		write(&bin, &crate::executable_name("java"), "");
+	}
+}
+
+/// Creates `bin/javac` within `home`, printing `version` like `javac -version` would.
+fn javac_stub(home: &Path, version: &str) {
+	let bin = home.join("bin");
+
+	#[cfg(unix)]
+	{
+		use std::os::unix::fs::PermissionsExt;
+
+		write(&bin, "javac", &format!("#!/bin/sh\necho 'javac {}'\n", version));
+
+		let javac = bin.join("javac");
+		fs::set_permissions(&javac, fs::Permissions::from_mode(0o755))
+			.expect("unable to mark fake JDK's javac executable as executable");
+	}
+
+	#[cfg(not(unix))]
+	{
+		let _ = version;
+		write(&bin, &crate::executable_name("javac"), "");
+	}
+}
+
+/// Writes a file within `dir`, creating `dir` if it does not exist.
+fn write(dir: &Path, file: &str, contents: &str) {
+	fs::create_dir_all(dir).expect("unable to create fake JDK directory");
+	fs::write(dir.join(file), contents).expect("unable to create fake JDK file");
+}
@@ -0,0 +1,157 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::Error;
+
+/// A parsed Java version, such as `17.0.2+8` or `1.8.0_292-b10`.
+///
+/// Legacy `1.x` versions are mapped to the modern scheme, with the update number as the patch version - `1.8.0_292` has a `major` of 8, a `minor` of 0, and a `patch` of 292.
+///
+/// Versions are ordered by `major`, `minor`, and `patch`, with pre-release versions ordering before their release, then by `build`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct JavaVersion {
+	/// The feature release number (`17` in `17.0.2`)
+	pub major: u32,
+	/// The interim release number (`0` in `17.0.2`)
+	pub minor: u32,
+	/// The update release number (`2` in `17.0.2`, `292` in `1.8.0_292`)
+	pub patch: u32,
+	/// The build number, if present (`8` in `17.0.2+8`, `10` in `1.8.0_292-b10`)
+	pub build: Option<u32>,
+	/// The pre-release identifier, if present (`ea` in `21-ea`)
+	pub pre: Option<String>,
+}
+impl JavaVersion {
+	/// The feature releases designated as long-term support (LTS) releases by Oracle and most OpenJDK distributors.
+	///
+	/// Since Java 17, a new LTS release is made every two years (every fourth feature release).
+	pub const LTS_RELEASES: &'static [u32] = &[8, 11, 17, 21, 25];
+
+	/// Checks if this version belongs to a long-term support release line, as listed in `JavaVersion::LTS_RELEASES`.
+	pub fn is_lts(&self) -> bool {
+		JavaVersion::LTS_RELEASES.contains(&self.major)
+	}
+}
+impl FromStr for JavaVersion {
+	type Err = Error;
+
+	/// Parses a version string, as found in the `java.version` property or `release` file.
+	fn from_str(s: &str) -> Result<JavaVersion, Error> {
+		let bad = || Error::VersionParse(s.to_string());
+
+		// split off the build (`+8`), then the pre-release identifier or legacy build (`-ea`, `-b10`)
+		let (rest, build) = match s.trim().split_once('+') {
+			Some((rest, build)) => (rest, Some(build)),
+			None => (s.trim(), None),
+		};
+		let (numbers, pre) = match rest.split_once('-') {
+			Some((numbers, pre)) => (numbers, Some(pre)),
+			None => (rest, None),
+		};
+
+		// legacy builds are part of the pre-release section (`1.8.0_292-b10`)
+		let (pre, build) = match (pre, build) {
+			(Some(pre), None) if pre.starts_with('b') && pre[1..].chars().all(|c| c.is_ascii_digit()) && pre.len() > 1 => (None, Some(&pre[1..])),
+			other => other,
+		};
+		// the build may carry extra data (`21-ea+35-2513`)
+		let build = build
+			.map(|build| build.split(|c: char| !c.is_ascii_digit()).next().unwrap_or("").parse::<u32>().map_err(|_| bad()))
+			.transpose()?;
+
+		let (numbers, update) = match numbers.split_once('_') {
+			Some((numbers, update)) => (numbers, Some(update.parse::<u32>().map_err(|_| bad())?)),
+			None => (numbers, None),
+		};
+		let mut parts = numbers.split('.').map(|part| part.parse::<u32>().map_err(|_| bad()));
+		let mut major = parts.next().ok_or_else(bad)??;
+		let mut minor = parts.next().transpose()?.unwrap_or(0);
+		let mut patch = parts.next().transpose()?.unwrap_or(0);
+
+		// legacy `1.x.y_z` scheme
+		if major == 1 && minor > 0 {
+			major = minor;
+			minor = patch;
+			patch = update.unwrap_or(0);
+		}
+
+		Ok(JavaVersion {
+			major,
+			minor,
+			patch,
+			build,
+			pre: pre.filter(|pre| !pre.is_empty()).map(String::from),
+		})
+	}
+}
+impl fmt::Display for JavaVersion {
+	/// Formats the version in the scheme Java itself would use for it - legacy (`1.8.0_292`) for Java 8 and earlier, otherwise modern (`17.0.2`).
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		if self.major <= 8 {
+			write!(f, "1.{}.{}", self.major, self.minor)?;
+			if self.patch != 0 {
+				write!(f, "_{}", self.patch)?;
+			}
+			if let Some(pre) = &self.pre {
+				write!(f, "-{}", pre)?;
+			}
+			if let Some(build) = self.build {
+				write!(f, "-b{:02}", build)?;
+			}
+		} else {
+			write!(f, "{}", self.major)?;
+			if self.minor != 0 || self.patch != 0 {
+				write!(f, ".{}.{}", self.minor, self.patch)?;
+			}
+			if let Some(pre) = &self.pre {
+				write!(f, "-{}", pre)?;
+			}
+			if let Some(build) = self.build {
+				write!(f, "+{}", build)?;
+			}
+		}
+		Ok(())
+	}
+}
+impl Ord for JavaVersion {
+	fn cmp(&self, other: &JavaVersion) -> Ordering {
+		(self.major, self.minor, self.patch).cmp(&(other.major, other.minor, other.patch))
+			// pre-releases come before the release itself
+			.then_with(|| match (&self.pre, &other.pre) {
+				(None, None) => Ordering::Equal,
+				(Some(_), None) => Ordering::Less,
+				(None, Some(_)) => Ordering::Greater,
+				(Some(a), Some(b)) => a.cmp(b),
+			})
+			.then_with(|| self.build.cmp(&other.build))
+	}
+}
+impl PartialOrd for JavaVersion {
+	fn partial_cmp(&self, other: &JavaVersion) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parses_version_schemes() {
+		let version = |major, minor, patch, build, pre: Option<&str>| JavaVersion { major, minor, patch, build, pre: pre.map(String::from) };
+		let cases = [
+			("1.8.0_292-b10", version(8, 0, 292, Some(10), None)),
+			("17.0.2+8", version(17, 0, 2, Some(8), None)),
+			("21-ea+35-2513", version(21, 0, 0, Some(35), Some("ea"))),
+			("11.0.14.1", version(11, 0, 14, None, None)),
+		];
+		for (s, expected) in &cases {
+			assert_eq!(&s.parse::<JavaVersion>().unwrap(), expected, "{}", s);
+		}
+
+		assert!(matches!("".parse::<JavaVersion>(), Err(Error::VersionParse(_))));
+		assert!(matches!("seventeen".parse::<JavaVersion>(), Err(Error::VersionParse(_))));
+	}
+}
@@ -0,0 +1,83 @@
+//! Recursive directory walking, for searching installations without the UTF-8 restrictions of glob patterns.
+
+use std::collections::HashSet;
+use std::fs::Metadata;
+use std::io;
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+
+/// Walks the directory tree under `base` (not including `base` itself), calling `visit` with each entry's path and metadata until it returns `ControlFlow::Break`.
+///
+/// Entries are visited in the same order a `base/**/*` glob would produce them - depth-first, with each directory's entries sorted by name and each subdirectory descended into as soon as it is visited. Symlinks are followed (and dangling ones skipped), with each directory visited at most once to guard against cycles.
+pub(crate) fn walk(base: &Path, mut visit: impl FnMut(&Path, &Metadata) -> ControlFlow<()>) -> io::Result<()> {
+	let mut seen = HashSet::new();
+	if let Ok(canonical) = base.canonicalize() {
+		seen.insert(canonical);
+	}
+	walk_dir(base, &mut visit, &mut seen).map(|_| ())
+}
+
+fn walk_dir(dir: &Path, visit: &mut impl FnMut(&Path, &Metadata) -> ControlFlow<()>, seen: &mut HashSet<PathBuf>) -> io::Result<ControlFlow<()>> {
+	let mut entries = std::fs::read_dir(dir)?
+		.map(|entry| entry.map(|entry| entry.path()))
+		.collect::<io::Result<Vec<PathBuf>>>()?;
+	entries.sort();
+
+	for path in entries {
+		let meta = match path.metadata() {
+			Ok(meta) => meta,
+			Err(e) if e.kind() == io::ErrorKind::NotFound => continue, // dangling symlink
+			Err(e) => return Err(e),
+		};
+
+		if visit(&path, &meta).is_break() {
+			return Ok(ControlFlow::Break(()));
+		}
+		if meta.is_dir() && seen.insert(path.canonicalize()?) && walk_dir(&path, visit, seen)?.is_break() {
+			return Ok(ControlFlow::Break(()));
+		}
+	}
+
+	Ok(ControlFlow::Continue(()))
+}
+
+/// Finds the first entry under `base` (in glob order) for which `pred` returns true.
+pub(crate) fn find(base: &Path, mut pred: impl FnMut(&Path, &Metadata) -> bool) -> io::Result<Option<PathBuf>> {
+	let mut found = None;
+	walk(base, |path, meta| {
+		if pred(path, meta) {
+			found = Some(path.to_path_buf());
+			ControlFlow::Break(())
+		} else {
+			ControlFlow::Continue(())
+		}
+	})?;
+	Ok(found)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn visits_in_glob_order() {
+		let dir = tempfile::tempdir().unwrap();
+		for file in &["a/x/f", "a/f", "a/y/z/f", "b/f", "f"] {
+			let path = dir.path().join(file);
+			std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+			std::fs::write(path, "").unwrap();
+		}
+
+		let mut visited = Vec::new();
+		walk(dir.path(), |path, _| {
+			visited.push(path.strip_prefix(dir.path()).unwrap().to_path_buf());
+			ControlFlow::Continue(())
+		}).unwrap();
+
+		let expected: Vec<PathBuf> = ["a", "a/f", "a/x", "a/x/f", "a/y", "a/y/z", "a/y/z/f", "b", "b/f", "f"]
+			.iter()
+			.map(PathBuf::from)
+			.collect();
+		assert_eq!(visited, expected);
+	}
+}